@@ -0,0 +1,428 @@
+use std::{
+    fs,
+    process::{Child, Command},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    godot_service::godot_engine_version::GodotEngineVersion,
+    project::{
+        export_preset::{read_export_presets, resolve_export_path},
+        launch_profile::{LaunchMode, LaunchProfile},
+        project_data::ProjectData,
+        project_service::project_directory,
+    },
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum WindowMode {
+    Windowed,
+    Fullscreen,
+}
+
+/// A task that is run on a project without opening a window
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum ProjectTask {
+    /// Exports the project with the preset named `preset`, to `output_path` or the preset's own export path
+    Export {
+        preset: String,
+        #[serde(rename(serialize = "outputPath", deserialize = "outputPath"), default)]
+        output_path: Option<String>,
+        debug: bool,
+    },
+    /// Imports all changed resources and quits
+    Import,
+    /// Runs a script extending `SceneTree` or `MainLoop`, e.g. `res://tools/generate_levels.gd`
+    Script { path: String },
+}
+
+impl ProjectTask {
+    /// Whether the task imports resources into the project's `.godot` folder, which an open editor writes to as well
+    pub fn imports_resources(&self) -> bool {
+        matches!(self, ProjectTask::Export { .. } | ProjectTask::Import)
+    }
+}
+
+/// Builds the command that launches `project` with `engine` using the options of `profile`
+pub fn build_project_command(
+    project: &ProjectData,
+    engine: &GodotEngineVersion,
+    profile: &LaunchProfile,
+) -> Command {
+    let directory = project_directory(&project.project_path);
+    let mut command = Command::new(&engine.executable_path);
+
+    if profile.mode == LaunchMode::Editor {
+        command.arg("--editor");
+    }
+
+    command
+        .arg("--path")
+        .arg(&directory)
+        .args(&profile.arguments)
+        .envs(&profile.environment_variables);
+
+    match &profile.working_directory {
+        Some(working_directory) if !working_directory.is_empty() => {
+            command.current_dir(working_directory)
+        }
+        _ => command.current_dir(&directory),
+    };
+
+    command
+}
+
+pub fn open_project(
+    project: &ProjectData,
+    engine: &GodotEngineVersion,
+    profile: &LaunchProfile,
+) -> std::io::Result<Child> {
+    build_project_command(project, engine, profile).spawn()
+}
+
+/// Builds the command that runs the game of `project` without the editor. Runs `scene` when given, e.g. `res://levels/level_1.tscn`,
+/// otherwise the project's main scene.
+pub fn build_run_command(
+    project: &ProjectData,
+    engine: &GodotEngineVersion,
+    profile: &LaunchProfile,
+    scene: Option<&str>,
+    window_mode: &WindowMode,
+) -> Command {
+    let mut game_profile = profile.clone();
+    game_profile.mode = LaunchMode::Game;
+
+    let mut command = build_project_command(project, engine, &game_profile);
+
+    match window_mode {
+        WindowMode::Windowed => command.arg("--windowed"),
+        WindowMode::Fullscreen => command.arg("--fullscreen"),
+    };
+
+    if let Some(scene) = scene {
+        command.arg(scene);
+    }
+
+    command
+}
+
+/// Builds the command that runs `task` on `project` headlessly. Godot 3 engines use their older flags
+/// (`--no-window`, `--export`) and engines before 4.2, which have no `--import`, import by opening and quitting the editor.
+pub fn build_task_command(
+    project: &ProjectData,
+    engine: &GodotEngineVersion,
+    task: &ProjectTask,
+) -> Result<Command, String> {
+    let directory = project_directory(&project.project_path);
+    let version = engine.major_minor().unwrap_or((4, 0));
+    let is_godot_3 = version.0 < 4;
+
+    let mut command = Command::new(&engine.executable_path);
+    command
+        .arg(if is_godot_3 {
+            "--no-window"
+        } else {
+            "--headless"
+        })
+        .arg("--path")
+        .arg(&directory)
+        .current_dir(&directory);
+
+    match task {
+        ProjectTask::Export {
+            preset,
+            output_path,
+            debug,
+        } => {
+            let export_preset = read_export_presets(&directory)
+                .into_iter()
+                .find(|export_preset| &export_preset.name == preset)
+                .ok_or(format!(
+                    "Did not find an export preset with name {} for project {}",
+                    preset, project.project_name
+                ))?;
+            let export_path =
+                resolve_export_path(&directory, &export_preset, output_path.as_deref())?;
+
+            // Godot fails the export if the target folder doesn't exist
+            if let Some(parent) = export_path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|error| format!("Could not create {}: {}", parent.display(), error))?;
+            }
+
+            let flag = match (is_godot_3, debug) {
+                (_, true) => "--export-debug",
+                (true, false) => "--export",
+                (false, false) => "--export-release",
+            };
+            command.arg(flag).arg(preset).arg(export_path);
+        }
+        ProjectTask::Import if version >= (4, 2) => {
+            command.arg("--import");
+        }
+        ProjectTask::Import => {
+            command.arg("--editor").arg("--quit");
+        }
+        ProjectTask::Script { path } => {
+            command.arg("--script").arg(path);
+        }
+    };
+
+    Ok(command)
+}
+
+/// Builds the command that converts the Godot 3 project `project` in place with the Godot 4 `engine`.
+/// A dry run uses `--validate-conversion-3to4`, Godot's dry run of `--convert-3to4`, which only reports what would change.
+pub fn build_convert_command(
+    project: &ProjectData,
+    engine: &GodotEngineVersion,
+    dry_run: bool,
+) -> Command {
+    let directory = project_directory(&project.project_path);
+
+    let mut command = Command::new(&engine.executable_path);
+    command
+        .arg("--headless")
+        .arg("--path")
+        .arg(&directory)
+        .arg(if dry_run {
+            "--validate-conversion-3to4"
+        } else {
+            "--convert-3to4"
+        })
+        .current_dir(&directory);
+
+    command
+}
+
+/// Builds the command that opens `engine` on its own, which shows Godot's project list
+pub fn build_engine_command(engine: &GodotEngineVersion) -> Command {
+    Command::new(&engine.executable_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, ffi::OsStr, fs, path::Path, process::Command};
+
+    use chrono::Local;
+
+    use crate::{
+        command::command::{
+            build_convert_command, build_project_command, build_run_command, build_task_command,
+            ProjectTask, WindowMode,
+        },
+        godot_service::godot_engine_version::GodotEngineVersion,
+        project::{
+            launch_profile::{LaunchMode, LaunchProfile},
+            project_data::ProjectData,
+        },
+    };
+
+    #[test]
+    fn test_build_project_command() {
+        let project = ProjectData::new(
+            "./test-data-launch/my_game/project.godot".to_string(),
+            "Godot_v4.2.1-stable_win64".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        );
+        let mut engine = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+            "./test-data-launch/Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+        );
+        engine.executable_path =
+            "./test-data-launch/Godot_v4.2.1-stable_win64/Godot_v4.2.1-stable_win64.exe"
+                .to_string();
+
+        let editor = build_project_command(&project, &engine, &LaunchProfile::default_editor());
+        let arguments: Vec<&OsStr> = editor.get_args().collect();
+
+        assert!(arguments == vec!["--editor", "--path", "./test-data-launch/my_game"]);
+        assert!(editor.get_current_dir() == Some(Path::new("./test-data-launch/my_game")));
+
+        let game = build_project_command(
+            &project,
+            &engine,
+            &LaunchProfile {
+                name: "Verbose".to_string(),
+                mode: LaunchMode::Game,
+                arguments: vec!["--verbose".to_string()],
+                environment_variables: HashMap::from([(
+                    "DOTNET_ROOT".to_string(),
+                    "/usr/share/dotnet".to_string(),
+                )]),
+                working_directory: Some("./test-data-launch".to_string()),
+            },
+        );
+        let arguments: Vec<&OsStr> = game.get_args().collect();
+
+        assert!(arguments == vec!["--path", "./test-data-launch/my_game", "--verbose"]);
+        assert!(game
+            .get_envs()
+            .any(|(key, value)| key == "DOTNET_ROOT"
+                && value == Some(OsStr::new("/usr/share/dotnet"))));
+        assert!(game.get_current_dir() == Some(Path::new("./test-data-launch")));
+    }
+
+    #[tokio::test]
+    async fn test_command() {
+        let output = if cfg!(target_os = "windows") {
+            Command::new("cmd")
+                .args(["/C", "echo hello"])
+                .output()
+                .expect("failed to execute process")
+        } else {
+            Command::new("sh")
+                .arg("-c")
+                .arg("echo hello")
+                .output()
+                .expect("failed to execute process")
+        };
+    }
+
+    #[test]
+    fn test_build_run_command() {
+        let project = ProjectData::new(
+            "./test-data-launch/my_game/project.godot".to_string(),
+            "Godot_v4.2.1-stable_win64".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        );
+        let engine = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+            "./test-data-launch/Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+        );
+
+        let command = build_run_command(
+            &project,
+            &engine,
+            &LaunchProfile::default_editor(),
+            Some("res://levels/level_1.tscn"),
+            &WindowMode::Fullscreen,
+        );
+        let arguments: Vec<&OsStr> = command.get_args().collect();
+
+        assert!(
+            arguments
+                == vec![
+                    "--path",
+                    "./test-data-launch/my_game",
+                    "--fullscreen",
+                    "res://levels/level_1.tscn"
+                ]
+        );
+    }
+
+    #[test]
+    fn test_build_task_command() {
+        let project_directory = Path::new("./test-data-tasks/my_game");
+        fs::create_dir_all(project_directory).unwrap();
+        fs::write(
+            project_directory.join("export_presets.cfg"),
+            "[preset.0]\n\nname=\"Linux\"\nplatform=\"Linux/X11\"\nexport_path=\"build/game.x86_64\"\n",
+        )
+        .unwrap();
+
+        let project = ProjectData::new(
+            "./test-data-tasks/my_game/project.godot".to_string(),
+            "Godot_v4.2.1-stable_win64".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        );
+        let godot_4 = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+            "./test-data-tasks/Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+        );
+        let godot_3 = GodotEngineVersion::new(
+            "Godot_v3.5.3-stable_win64".to_string(),
+            "".to_string(),
+            "./test-data-tasks/Godot_v3.5.3-stable_win64".to_string(),
+            "".to_string(),
+        );
+
+        let export = ProjectTask::Export {
+            preset: "Linux".to_string(),
+            output_path: None,
+            debug: false,
+        };
+        let command = build_task_command(&project, &godot_4, &export).unwrap();
+        let arguments: Vec<&OsStr> = command.get_args().collect();
+
+        assert!(arguments[0] == "--headless");
+        assert!(arguments[3] == "--export-release");
+        assert!(arguments[4] == "Linux");
+        assert!(Path::new(arguments[5]) == project_directory.join("build/game.x86_64"));
+        assert!(project_directory.join("build").exists());
+
+        let command = build_task_command(&project, &godot_3, &export).unwrap();
+        let arguments: Vec<&OsStr> = command.get_args().collect();
+
+        assert!(arguments[0] == "--no-window");
+        assert!(arguments[3] == "--export");
+
+        let command = build_task_command(&project, &godot_3, &ProjectTask::Import).unwrap();
+        let arguments: Vec<&OsStr> = command.get_args().collect();
+
+        assert!(arguments[3..] == ["--editor", "--quit"]);
+
+        let missing = ProjectTask::Export {
+            preset: "Web".to_string(),
+            output_path: None,
+            debug: false,
+        };
+        assert!(build_task_command(&project, &godot_4, &missing).is_err());
+
+        assert!(export.imports_resources());
+        assert!(ProjectTask::Import.imports_resources());
+        assert!(!ProjectTask::Script {
+            path: "res://tools/generate_levels.gd".to_string()
+        }
+        .imports_resources());
+
+        fs::remove_dir_all("./test-data-tasks").unwrap();
+    }
+
+    #[test]
+    fn test_build_convert_command() {
+        let project = ProjectData::new(
+            "./test-data-convert/old_game/project.godot".to_string(),
+            "Godot_v3.5.3-stable_win64".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        );
+        let engine = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+            "./test-data-convert/Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+        );
+
+        let dry_run = build_convert_command(&project, &engine, true);
+        let arguments: Vec<&OsStr> = dry_run.get_args().collect();
+
+        assert!(
+            arguments
+                == vec![
+                    "--headless",
+                    "--path",
+                    "./test-data-convert/old_game",
+                    "--validate-conversion-3to4"
+                ]
+        );
+
+        let convert = build_convert_command(&project, &engine, false);
+        assert!(convert.get_args().last() == Some(OsStr::new("--convert-3to4")));
+    }
+}
@@ -6,8 +6,12 @@ use std::{
 use directories::BaseDirs;
 
 use crate::{
-    godot_service::godot_engine_version::GodotEngineVersion,
-    project::project_data::{ProjectConfig, ProjectData},
+    godot_service::{godot_engine_version::GodotEngineVersion, release_channels::ReleaseFilter},
+    news::news_cache::NewsSettings,
+    project::{
+        project_backup::BackupSettings,
+        project_data::{ProjectConfig, ProjectData},
+    },
 };
 
 pub struct ConfigDirectoryService {
@@ -21,6 +25,11 @@ impl ConfigDirectoryService {
     pub fn new() -> ConfigDirectoryService {
         let mut storage_path = BaseDirs::new().unwrap().config_dir().to_path_buf();
         storage_path.push("godot_project_manager");
+        Self::with_root(storage_path)
+    }
+
+    /// Creates a service that keeps the config file, engines and all other data under `storage_path`
+    pub fn with_root(storage_path: PathBuf) -> ConfigDirectoryService {
         ConfigDirectoryService {
             config_file_name: "config.json".to_string(),
             storage_path: storage_path.clone(),
@@ -82,6 +91,63 @@ impl ConfigDirectoryService {
         path
     }
 
+    /// Gets the folder where downloads that are not engine archives (e.g. export templates) are kept
+    /// until they are installed
+    pub fn download_cache_path(&self) -> PathBuf {
+        let mut path = PathBuf::from(&self.storage_path);
+        path.push("downloads");
+        path
+    }
+
+    /// Gets the file where the releases of the last catalog fetch are kept for offline use
+    pub fn release_cache_path(&self) -> PathBuf {
+        let mut path = PathBuf::from(&self.storage_path);
+        path.push("releases.json");
+        path
+    }
+
+    /// Gets the folder where the output of launched editors and games is logged
+    pub fn logs_path(&self) -> PathBuf {
+        let mut path = PathBuf::from(&self.storage_path);
+        path.push("logs");
+        path
+    }
+
+    /// Gets the folder where the usage history of each project is kept
+    pub fn history_path(&self) -> PathBuf {
+        let mut path = PathBuf::from(&self.storage_path);
+        path.push("history");
+        path
+    }
+
+    /// Gets the folder where copies of project icons and screenshots are kept for the project list
+    pub fn thumbnail_cache_path(&self) -> PathBuf {
+        let mut path = PathBuf::from(&self.storage_path);
+        path.push("thumbnails");
+        path
+    }
+
+    /// Gets the folder where project snapshots are kept
+    pub fn backups_path(&self) -> PathBuf {
+        let mut path = PathBuf::from(&self.storage_path);
+        path.push("backups");
+        path
+    }
+
+    /// Gets the folder where the news entries and their thumbnails are cached
+    pub fn news_path(&self) -> PathBuf {
+        let mut path = PathBuf::from(&self.storage_path);
+        path.push("news");
+        path
+    }
+
+    /// Gets the folder engines are moved to while they are being deleted
+    pub fn trash_path(&self) -> PathBuf {
+        let mut path = PathBuf::from(&self.storage_path);
+        path.push("trash");
+        path
+    }
+
     fn create_config_path_if_not_exsits(&self, storage_path: &Path) {
         let mut path = PathBuf::from(storage_path);
         if !path.exists() {
@@ -97,6 +163,9 @@ impl ConfigDirectoryService {
                 tracked_directories: vec![],
                 tracked_godot_versions: vec![],
                 tracked_projects: vec![],
+                backup_settings: BackupSettings::default(),
+                release_filter: ReleaseFilter::default(),
+                news_settings: NewsSettings::default(),
             };
 
             fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
@@ -169,6 +238,39 @@ pub fn save_tracked_directories_to_config(
     save_project_config(directory, &data);
 }
 
+pub fn save_backup_settings_to_config(
+    directory: &ConfigDirectoryService,
+    backup_settings: &BackupSettings,
+) {
+    let mut data: ProjectConfig = get_project_config(directory);
+
+    data.backup_settings = backup_settings.clone();
+
+    save_project_config(directory, &data);
+}
+
+pub fn save_release_filter_to_config(
+    directory: &ConfigDirectoryService,
+    release_filter: &ReleaseFilter,
+) {
+    let mut data: ProjectConfig = get_project_config(directory);
+
+    data.release_filter = release_filter.clone();
+
+    save_project_config(directory, &data);
+}
+
+pub fn save_news_settings_to_config(
+    directory: &ConfigDirectoryService,
+    news_settings: &NewsSettings,
+) {
+    let mut data: ProjectConfig = get_project_config(directory);
+
+    data.news_settings = news_settings.clone();
+
+    save_project_config(directory, &data);
+}
+
 fn write_existing_projects_to_config(
     directory: &ConfigDirectoryService,
     projects: &Vec<ProjectData>,
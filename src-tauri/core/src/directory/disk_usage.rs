@@ -0,0 +1,65 @@
+use std::{fs, path::Path};
+
+/// Gets the total size in bytes of all files in `path` and its subfolders. Symlinks are not followed.
+/// Returns 0 if the folder doesn't exist.
+pub fn directory_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => directory_size(&entry.path()),
+            Ok(file_type) if file_type.is_file() => {
+                entry.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+            }
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Formats a size in bytes for display, e.g. `1.5 GB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{directory_size, format_size};
+
+    #[test]
+    fn test_directory_size() {
+        let path = PathBuf::from("./test-data-disk-usage");
+        fs::create_dir_all(path.join("nested")).unwrap();
+        fs::write(path.join("a.txt"), vec![0u8; 100]).unwrap();
+        fs::write(path.join("nested").join("b.txt"), vec![0u8; 50]).unwrap();
+
+        assert!(directory_size(&path) == 150);
+        assert!(directory_size(&path.join("missing")) == 0);
+
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert!(format_size(512) == "512 B");
+        assert!(format_size(1536) == "1.5 KB");
+        assert!(format_size(3 * 1024 * 1024 * 1024) == "3.0 GB");
+    }
+}
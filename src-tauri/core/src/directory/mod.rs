@@ -1,2 +1,4 @@
 pub mod config_directory_service;
+pub mod disk_usage;
+pub mod storage_report;
 pub mod tests;
@@ -0,0 +1,396 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    godot_service::{export_template_service, godot_engine_version::GodotEngineVersion},
+    project::{
+        project_cache::{self, ProjectCache},
+        project_data::ProjectData,
+        project_service::project_directory,
+    },
+};
+
+use super::{
+    config_directory_service::ConfigDirectoryService,
+    disk_usage::{directory_size, format_size},
+};
+
+/// The disk space used by an installed engine
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EngineUsage {
+    #[serde(rename(serialize = "engineName", deserialize = "engineName"))]
+    pub engine_name: String,
+    /// The extracted engine, without its leftover archives and self-contained export templates
+    #[serde(rename(serialize = "installSize", deserialize = "installSize"))]
+    pub install_size: u64,
+    /// The downloaded archives left next to the extracted engine
+    #[serde(rename(serialize = "archivePaths", deserialize = "archivePaths"))]
+    pub archive_paths: Vec<String>,
+    #[serde(rename(serialize = "archiveSize", deserialize = "archiveSize"))]
+    pub archive_size: u64,
+    #[serde(rename(serialize = "exportTemplatesSize", deserialize = "exportTemplatesSize"))]
+    pub export_templates_size: u64,
+    /// The tracked projects assigned to the engine
+    #[serde(rename(serialize = "usedBy", deserialize = "usedBy"))]
+    pub used_by: Vec<String>,
+}
+
+impl EngineUsage {
+    pub fn total_size(&self) -> u64 {
+        self.install_size + self.archive_size + self.export_templates_size
+    }
+}
+
+/// A file or folder in the engines folder that belongs to no installed engine, e.g. an interrupted download
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UnregisteredEngineFolder {
+    pub path: String,
+    pub size: u64,
+}
+
+/// How much space the manager uses, with suggestions to free some of it
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StorageReport {
+    pub engines: Vec<EngineUsage>,
+    #[serde(rename(serialize = "unregisteredEngines", deserialize = "unregisteredEngines"))]
+    pub unregistered_engines: Vec<UnregisteredEngineFolder>,
+    /// Engines left in the trash folder by removals that failed part way
+    #[serde(rename(serialize = "trashSize", deserialize = "trashSize"))]
+    pub trash_size: u64,
+    #[serde(rename(serialize = "downloadCacheSize", deserialize = "downloadCacheSize"))]
+    pub download_cache_size: u64,
+    #[serde(rename(serialize = "backupsSize", deserialize = "backupsSize"))]
+    pub backups_size: u64,
+    #[serde(rename(serialize = "logsSize", deserialize = "logsSize"))]
+    pub logs_size: u64,
+    #[serde(rename(serialize = "projectCaches", deserialize = "projectCaches"))]
+    pub project_caches: Vec<ProjectCache>,
+    #[serde(rename(serialize = "totalSize", deserialize = "totalSize"))]
+    pub total_size: u64,
+    pub suggestions: Vec<String>,
+}
+
+/// Finds the archives a download left in an engine folder. They are no longer needed once the engine is extracted.
+pub fn find_engine_archives(engine_path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(engine_path) else {
+        return vec![];
+    };
+
+    let mut archives: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file() && path.extension().is_some_and(|extension| extension == "zip")
+        })
+        .collect();
+
+    archives.sort();
+    archives
+}
+
+pub fn engine_usage(engine: &GodotEngineVersion, projects: &[ProjectData]) -> EngineUsage {
+    let engine_path = PathBuf::from(&engine.path);
+    let archives = find_engine_archives(&engine_path);
+    let archive_size: u64 = archives
+        .iter()
+        .map(|archive| {
+            fs::metadata(archive)
+                .map(|metadata| metadata.len())
+                .unwrap_or(0)
+        })
+        .sum();
+
+    let templates_path = export_template_service::export_templates_path(engine);
+    let export_templates_size = templates_path
+        .as_ref()
+        .map(|path| directory_size(path))
+        .unwrap_or(0);
+
+    // Self-contained engines keep their templates inside the engine folder
+    let templates_inside = templates_path.is_some_and(|path| path.starts_with(&engine_path));
+    let install_size = directory_size(&engine_path)
+        .saturating_sub(archive_size)
+        .saturating_sub(if templates_inside {
+            export_templates_size
+        } else {
+            0
+        });
+
+    EngineUsage {
+        engine_name: engine.version_name.clone(),
+        install_size,
+        archive_paths: archives
+            .iter()
+            .map(|archive| archive.to_string_lossy().to_string())
+            .collect(),
+        archive_size,
+        export_templates_size,
+        used_by: projects
+            .iter()
+            .filter(|project| project.engine_version == engine.version_name)
+            .map(|project| project.project_name.clone())
+            .collect(),
+    }
+}
+
+/// Finds the entries of the engines folder that don't hold any of the installed `engines`
+pub fn find_unregistered_engine_folders(
+    engine_storage_path: &Path,
+    engines: &[GodotEngineVersion],
+) -> Vec<UnregisteredEngineFolder> {
+    let Ok(entries) = fs::read_dir(engine_storage_path) else {
+        return vec![];
+    };
+
+    let canonical = |path: &Path| fs::canonicalize(path).unwrap_or(path.to_path_buf());
+    let engine_paths: Vec<PathBuf> = engines
+        .iter()
+        .map(|engine| canonical(Path::new(&engine.path)))
+        .collect();
+
+    let mut folders: Vec<UnregisteredEngineFolder> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let path = canonical(path);
+            !engine_paths
+                .iter()
+                .any(|engine_path| engine_path.starts_with(&path))
+        })
+        .map(|path| UnregisteredEngineFolder {
+            size: if path.is_dir() {
+                directory_size(&path)
+            } else {
+                fs::metadata(&path)
+                    .map(|metadata| metadata.len())
+                    .unwrap_or(0)
+            },
+            path: path.to_string_lossy().to_string(),
+        })
+        .collect();
+
+    folders.sort_by(|a, b| a.path.cmp(&b.path));
+    folders
+}
+
+/// Measures the engines, downloads, backups and logs in the storage of `config_directory`
+/// and the caches of the tracked `projects`
+pub fn storage_report(
+    config_directory: &ConfigDirectoryService,
+    engines: &[GodotEngineVersion],
+    projects: &[ProjectData],
+) -> StorageReport {
+    let unregistered_engines =
+        find_unregistered_engine_folders(config_directory.engine_storage_path(), engines);
+    let engines: Vec<EngineUsage> = engines
+        .iter()
+        .map(|engine| engine_usage(engine, projects))
+        .collect();
+
+    let project_caches: Vec<ProjectCache> = projects
+        .iter()
+        .map(|project| {
+            project_cache::project_cache(
+                &project.project_name,
+                &project_directory(&project.project_path),
+            )
+        })
+        .collect();
+
+    let download_cache_size = directory_size(&config_directory.download_cache_path());
+    let backups_size = directory_size(&config_directory.backups_path());
+    let logs_size = directory_size(&config_directory.logs_path());
+    let trash_size = directory_size(&config_directory.trash_path());
+
+    let mut suggestions: Vec<String> = vec![];
+
+    for engine in &engines {
+        if engine.used_by.is_empty() {
+            suggestions.push(format!(
+                "{} is not used by any project, removing it frees {}",
+                engine.engine_name,
+                format_size(engine.total_size())
+            ));
+        }
+
+        if engine.archive_size > 0 {
+            suggestions.push(format!(
+                "{} still has its downloaded archive, deleting it frees {}",
+                engine.engine_name,
+                format_size(engine.archive_size)
+            ));
+        }
+    }
+
+    for folder in &unregistered_engines {
+        suggestions.push(format!(
+            "{} does not belong to an installed engine, deleting it frees {}",
+            folder.path,
+            format_size(folder.size)
+        ));
+    }
+
+    if trash_size > 0 {
+        suggestions.push(format!(
+            "The trash folder still holds removed engines, emptying it frees {}",
+            format_size(trash_size)
+        ));
+    }
+
+    let unregistered_size: u64 = unregistered_engines.iter().map(|folder| folder.size).sum();
+    let total_cache_size: u64 = project_caches.iter().map(|cache| cache.total_size).sum();
+    if total_cache_size > 0 {
+        suggestions.push(format!(
+            "Cleaning project caches frees up to {}, they are rebuilt on the next open",
+            format_size(total_cache_size)
+        ));
+    }
+
+    StorageReport {
+        total_size: engines
+            .iter()
+            .map(|engine| engine.total_size())
+            .sum::<u64>()
+            + unregistered_size
+            + trash_size
+            + download_cache_size
+            + backups_size
+            + logs_size
+            + total_cache_size,
+        engines,
+        unregistered_engines,
+        trash_size,
+        download_cache_size,
+        backups_size,
+        logs_size,
+        project_caches,
+        suggestions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use chrono::Local;
+
+    use crate::{
+        directory::config_directory_service::ConfigDirectoryService,
+        godot_service::godot_engine_version::GodotEngineVersion,
+        project::project_data::ProjectData,
+    };
+
+    use super::storage_report;
+
+    fn engine(config_directory: &ConfigDirectoryService, name: &str) -> GodotEngineVersion {
+        let path = config_directory.engine_version_path(name);
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join(format!("{}.exe", name)), vec![0u8; 100]).unwrap();
+        // Keeps the export templates lookup inside the test folder
+        fs::write(path.join("_sc_"), "").unwrap();
+
+        GodotEngineVersion::new(
+            name.to_string(),
+            "".to_string(),
+            path.to_string_lossy().to_string(),
+            "".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_storage_report() {
+        let config_directory = ConfigDirectoryService::new_test(
+            "./test-data-storage-report".to_string(),
+            "config.json".to_string(),
+        );
+
+        let used = engine(&config_directory, "Godot_v4.2.1-stable_win64");
+        let unused = engine(&config_directory, "Godot_v4.1.3-stable_win64");
+        fs::write(
+            config_directory
+                .engine_version_path("Godot_v4.1.3-stable_win64")
+                .join("Godot_v4.1.3-stable_win64.exe.zip"),
+            vec![0u8; 40],
+        )
+        .unwrap();
+
+        // An interrupted download and an engine whose removal failed part way
+        fs::create_dir_all(config_directory.engine_version_path("Godot_v4.3-stable_win64"))
+            .unwrap();
+        fs::write(
+            config_directory
+                .engine_version_path("Godot_v4.3-stable_win64")
+                .join("Godot_v4.3-stable_win64.exe.zip"),
+            vec![0u8; 50],
+        )
+        .unwrap();
+        fs::create_dir_all(
+            config_directory
+                .trash_path()
+                .join("Godot_v4.0-stable_win64"),
+        )
+        .unwrap();
+        fs::write(
+            config_directory
+                .trash_path()
+                .join("Godot_v4.0-stable_win64")
+                .join("Godot_v4.0-stable_win64.exe"),
+            vec![0u8; 10],
+        )
+        .unwrap();
+
+        let project_directory = config_directory.storage_path().join("my_game");
+        fs::create_dir_all(project_directory.join(".godot").join("imported")).unwrap();
+        fs::write(
+            project_directory
+                .join(".godot")
+                .join("imported")
+                .join("icon.ctex"),
+            vec![0u8; 30],
+        )
+        .unwrap();
+        fs::create_dir_all(config_directory.backups_path()).unwrap();
+        fs::write(
+            config_directory.backups_path().join("backup.zip"),
+            vec![0u8; 20],
+        )
+        .unwrap();
+
+        let project = ProjectData::new(
+            project_directory
+                .join("project.godot")
+                .to_string_lossy()
+                .to_string(),
+            "Godot_v4.2.1-stable_win64".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        );
+
+        let report = storage_report(&config_directory, &[used, unused], &[project]);
+
+        assert!(report.engines[0].used_by == vec!["my_game".to_string()]);
+        assert!(report.engines[0].install_size == 100);
+        assert!(report.engines[1].used_by.is_empty());
+        assert!(report.engines[1].install_size == 100);
+        assert!(report.engines[1].archive_size == 40);
+        assert!(report.backups_size == 20);
+        assert!(report.project_caches[0].total_size == 30);
+        assert!(report.unregistered_engines.len() == 1);
+        assert!(report.unregistered_engines[0]
+            .path
+            .ends_with("Godot_v4.3-stable_win64"));
+        assert!(report.unregistered_engines[0].size == 50);
+        assert!(report.trash_size == 10);
+        assert!(report.total_size == 350);
+        assert!(report.suggestions.len() == 5);
+        assert!(report.suggestions[0].contains("Godot_v4.1.3-stable_win64 is not used"));
+        assert!(report.suggestions[2].contains("does not belong to an installed engine"));
+
+        fs::remove_dir_all("./test-data-storage-report").unwrap();
+    }
+}
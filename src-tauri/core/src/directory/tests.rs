@@ -7,8 +7,14 @@ mod tests {
         directory::config_directory_service::{
             get_project_config, save_project_config, ConfigDirectoryService,
         },
-        godot_service::godot_engine_version::GodotEngineVersion,
-        project::project_data::{ProjectConfig, ProjectData},
+        godot_service::{
+            godot_engine_version::GodotEngineVersion, release_channels::ReleaseFilter,
+        },
+        news::news_cache::NewsSettings,
+        project::{
+            project_backup::BackupSettings,
+            project_data::{ProjectConfig, ProjectData},
+        },
     };
 
     #[tokio::test]
@@ -32,6 +38,9 @@ mod tests {
             tracked_directories: vec!["test".to_string(), "test2".to_string()],
             tracked_godot_versions: vec![],
             tracked_projects: vec![],
+            backup_settings: BackupSettings::default(),
+            release_filter: ReleaseFilter::default(),
+            news_settings: NewsSettings::default(),
         };
 
         let directory = ConfigDirectoryService::new_test(
@@ -62,6 +71,9 @@ mod tests {
                 false,
                 false,
             )],
+            backup_settings: BackupSettings::default(),
+            release_filter: ReleaseFilter::default(),
+            news_settings: NewsSettings::default(),
         };
 
         let directory = ConfigDirectoryService::new_test(
@@ -91,6 +103,9 @@ mod tests {
                 "test".to_string(),
             )],
             tracked_projects: vec![],
+            backup_settings: BackupSettings::default(),
+            release_filter: ReleaseFilter::default(),
+            news_settings: NewsSettings::default(),
         };
 
         let directory = ConfigDirectoryService::new_test(
@@ -126,6 +141,9 @@ mod tests {
                 false,
                 false,
             )],
+            backup_settings: BackupSettings::default(),
+            release_filter: ReleaseFilter::default(),
+            news_settings: NewsSettings::default(),
         };
 
         let directory = ConfigDirectoryService::new_test(
@@ -0,0 +1,449 @@
+use std::{
+    fs,
+    io::{Cursor, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use reqwest::header::USER_AGENT;
+use serde::{Deserialize, Serialize};
+use tokio_stream::StreamExt;
+
+use crate::{
+    directory::config_directory_service::ConfigDirectoryService,
+    environmnet::is_prod,
+    godot_service::{
+        godot_engine_service, godot_engine_version::GodotEngineVersion, release_channels,
+    },
+    test_data,
+};
+
+use super::progress_reporter::ProgressReporter;
+
+use chrono::{DateTime, Local};
+
+const GITHUB_URL: &str = "https://api.github.com/repos/godotengine/godot/releases";
+const GITHUB_BETA_BUILDS_URL: &str = "https://api.github.com/repos/godotengine/godot-builds/releases";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Asset {
+    pub browser_download_url: String,
+    pub name: String,
+    pub created_at: String,
+    pub size: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Release {
+    assets: Vec<Asset>,
+    #[serde(default)]
+    pub tag_name: String,
+    /// The release notes in Markdown
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub html_url: String,
+    #[serde(default)]
+    pub published_at: Option<String>,
+}
+
+/// Gets all releases from https://api.github.com/repos/godotengine/godot/releases
+/// and https://api.github.com/repos/godotengine/godot-builds/releases
+///
+/// # Errors
+///
+/// This function will return an error if there was an error sending a request to the url, the api responded
+/// with an error such as its rate limit, or the body cannot be parsed from json into the Release object.
+pub async fn get_available_releases(
+    client: &reqwest::Client,
+) -> Result<Vec<Release>, Box<dyn std::error::Error>> {
+    get_available_releases_from(client, &[GITHUB_URL, GITHUB_BETA_BUILDS_URL]).await
+}
+
+/// Gets all releases from each of the GitHub release api `urls`, newest first
+pub async fn get_available_releases_from(
+    client: &reqwest::Client,
+    urls: &[&str],
+) -> Result<Vec<Release>, Box<dyn std::error::Error>> {
+    let mut releases: Vec<Release> = vec![];
+
+    for url in urls {
+        let body: String = client
+            .get(*url)
+            .header(USER_AGENT, "My Rust Program 1.0")
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        let url_releases: Vec<Release> = serde_json::from_str(&body)?;
+        releases.extend(url_releases);
+    }
+
+    // Releases without assets can't be installed
+    releases.retain(|release| !release.assets.is_empty());
+
+    // Sort releases by their date uploaded time
+    releases.sort_by_key(|release| {
+        release.assets.first().unwrap().created_at.clone().parse::<DateTime<Local>>().unwrap()
+    });
+    releases.reverse();
+
+    return Ok(releases);
+}
+
+/// Gets all releases and keeps them in the release cache, so the catalog and release notes are available offline.
+/// Falls back to the cached releases if they can't be fetched.
+pub async fn get_cached_releases(
+    client: &reqwest::Client,
+    directory_service: &ConfigDirectoryService,
+) -> Result<Vec<Release>, Box<dyn std::error::Error>> {
+    match get_available_releases(client).await {
+        Ok(releases) => {
+            save_release_cache(directory_service, &releases);
+            Ok(releases)
+        }
+        Err(error) => {
+            let cached = load_release_cache(directory_service);
+            if cached.is_empty() {
+                return Err(error);
+            }
+            Ok(cached)
+        }
+    }
+}
+
+pub fn save_release_cache(directory_service: &ConfigDirectoryService, releases: &[Release]) {
+    let serialized = serde_json::to_string(releases).unwrap();
+
+    if let Err(error) = fs::create_dir_all(directory_service.storage_path())
+        .and_then(|_| fs::write(directory_service.release_cache_path(), serialized))
+    {
+        println!("Could not write release cache: {}", error);
+    }
+}
+
+/// Gets the releases from the last successful fetch, or none if they were never fetched
+pub fn load_release_cache(directory_service: &ConfigDirectoryService) -> Vec<Release> {
+    fs::read_to_string(directory_service.release_cache_path())
+        .ok()
+        .and_then(|cached| serde_json::from_str(&cached).ok())
+        .unwrap_or_default()
+}
+
+pub async fn download_and_extract_engine(
+    client: &reqwest::Client,
+    directory_service: &ConfigDirectoryService,
+    godot_engine_version: &GodotEngineVersion,
+    self_contained: bool,
+    progress: &dyn ProgressReporter,
+) -> Result<GodotEngineVersion, Box<dyn std::error::Error>> {
+    godot_engine_service::validate_engine_name(&godot_engine_version.version_name)?;
+
+    let godot_engine_path = directory_service.engine_storage_path();
+    let engine_name = godot_engine_version.version_name.to_string();
+    let version_path = create_engine_version_path(&godot_engine_path, &engine_name);
+    let file_path = create_file_path_from_url_at_path(&version_path, &engine_name);
+
+    fs::File::create(&file_path).unwrap();
+
+    let archive: Vec<u8> = download_url(
+        client,
+        &godot_engine_version.download_url,
+        &file_path,
+        &engine_name,
+        progress,
+    )
+    .await?;
+
+    let target_dir = PathBuf::from(&version_path); // Doesn't need to exist
+
+    // The third parameter allows you to strip away toplevel directories.
+    // If `archive` contained a single folder, that folder's contents would be extracted instead.
+    zip_extract::extract(Cursor::new(archive), &target_dir, true)?;
+
+    progress.finish(&engine_name);
+
+    let mut installed_engine = GodotEngineVersion::new(
+        godot_engine_version.version_name.clone(),
+        godot_engine_version.updated_at.clone(),
+        version_path.to_str().unwrap().to_string(),
+        godot_engine_version.download_url.clone(),
+    );
+
+    if self_contained {
+        godot_engine_service::set_self_contained(&installed_engine, true, false)?;
+        installed_engine.self_contained = true;
+    }
+
+    Ok(installed_engine)
+}
+
+fn create_engine_version_path(godot_engine_path: &Path, engine_name: &str) -> PathBuf {
+    let mut path = PathBuf::from(godot_engine_path);
+    let name = &engine_name.replace(".exe", "");
+    let name2 = &name.replace(".zip", "");
+    let name3 = &name2.replace(" ", "_");
+    path.push(name3);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn create_file_path_from_url_at_path(folder_path: &Path, url: &str) -> PathBuf {
+    let mut file_path = PathBuf::from(folder_path);
+    let file_name = url.split("/").last().unwrap();
+    file_path.push(file_name);
+    file_path
+}
+
+pub async fn download_url(
+    client: &reqwest::Client,
+    url: &str,
+    file_path: &Path,
+    engine_name: &str,
+    progress: &dyn ProgressReporter,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    // let response = reqwest::get(url).await?;
+    // let mut file = std::fs::File::create(file_path)?;
+    // let mut content = Cursor::new(response.bytes().await?);
+    // std::io::copy(&mut content, &mut file)?;
+
+    // let byte_array = content.get_ref().to_vec().try_into().unwrap();
+
+    // Ok(byte_array)
+
+    let mut file = std::fs::File::create(file_path)?;
+
+    let response = client
+        .get(url)
+        .header(USER_AGENT, "My Rust Program 1.0")
+        .send()
+        .await?
+        .error_for_status()?;
+    let total_size: usize = response.content_length().unwrap_or(0) as usize;
+    let mut curr_size: usize = 0;
+
+    let stream = &mut response.bytes_stream();
+
+    while let Some(chunk_result) = stream.next().await {
+        let chunk = chunk_result?;
+        curr_size += chunk.len();
+        if total_size > 0 {
+            let current_progess = ((curr_size as f32 / total_size as f32) * 100f32) as usize;
+            progress.report(engine_name, current_progess);
+        }
+        file.write_all(&chunk)?;
+    }
+
+    file.flush()?;
+
+    let mut buffer = Vec::new();
+    fs::File::open(file_path)
+        .unwrap()
+        .read_to_end(&mut buffer)?;
+
+    Ok(buffer)
+}
+
+/// Gets every engine version that can be installed, newest first.
+/// Releases published to both repositories are only listed once.
+pub async fn get_engine_catalog(
+    client: &reqwest::Client,
+    directory_service: &ConfigDirectoryService,
+) -> Result<Vec<GodotEngineVersion>, Box<dyn std::error::Error>> {
+    let releases = get_cached_releases(client, directory_service).await?;
+    let assets = filter_assets_by_name(&releases, "win64");
+
+    Ok(release_channels::deduplicate_catalog(
+        assets
+            .into_iter()
+            .map(|asset| godot_engine_service::from_asset(asset))
+            .collect(),
+    ))
+}
+
+/// Filters assets by name
+pub fn filter_assets_by_name(releases: &Vec<Release>, filter: &str) -> Vec<Asset> {
+    let assets: Vec<&Asset> = releases
+        .into_iter()
+        .map(|release| &release.assets)
+        .flatten()
+        .collect();
+
+    let filtered: Vec<Asset> = assets
+        .into_iter()
+        .filter(|asset| asset.name.contains(&filter))
+        .map(|asset| asset.clone())
+        .collect();
+
+    filtered
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fs, path::PathBuf, sync::Mutex};
+
+    use crate::{
+        directory::config_directory_service::ConfigDirectoryService,
+        fetcher::{
+            download_service::{
+                self, download_and_extract_engine, get_available_releases,
+                get_available_releases_from,
+            },
+            os_type::OsType,
+            progress_reporter::{DownloadProgress, ProgressReporter},
+        },
+        godot_service::godot_engine_version::GodotEngineVersion,
+        test_data, test_server,
+    };
+
+    /// Records every progress update so tests can check what was reported
+    struct RecordingProgress(Mutex<Vec<(String, usize)>>, Mutex<Vec<String>>);
+
+    impl ProgressReporter for RecordingProgress {
+        fn report(&self, name: &str, progress: usize) {
+            self.0.lock().unwrap().push((name.to_string(), progress));
+        }
+
+        fn finish(&self, name: &str) {
+            self.1.lock().unwrap().push(name.to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_download_engine_version() {
+        let archive = test_server::build_zip(vec![
+            ("Godot_v4.2.1-stable_win64.exe", b"engine"),
+            ("Godot_v4.2.1-stable_win64_console.exe", b"console"),
+        ]);
+        let url = test_server::serve(vec![(
+            "/4.2.1-stable/Godot_v4.2.1-stable_win64.exe.zip",
+            archive,
+        )])
+        .await;
+
+        let directory_service = ConfigDirectoryService::new_test(
+            "./test-data-6".to_string(),
+            "test1.json".to_string(),
+        );
+
+        let engine = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64.exe.zip".to_string(),
+            "2024-01-20".to_string(),
+            "".to_string(),
+            format!("{}/4.2.1-stable/Godot_v4.2.1-stable_win64.exe.zip", url),
+        );
+
+        let progress = RecordingProgress(Mutex::new(vec![]), Mutex::new(vec![]));
+
+        let updated_engine = download_and_extract_engine(
+            &reqwest::Client::new(),
+            &directory_service,
+            &engine,
+            false,
+            &progress,
+        )
+        .await
+        .unwrap();
+
+        assert!(updated_engine.version_name == "Godot_v4.2.1-stable_win64");
+        assert!(updated_engine.version_number == "4.2.1");
+        assert!(!updated_engine.path.is_empty());
+        assert!(PathBuf::from(&updated_engine.executable_path).exists());
+        assert!(PathBuf::from(&updated_engine.console_executable_path).exists());
+        assert!(progress.0.lock().unwrap().last().unwrap().1 == 100);
+        assert!(progress.1.lock().unwrap().len() == 1);
+
+        fs::remove_dir_all("./test-data-6").unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_download_missing_engine_version() {
+        let url = test_server::serve(vec![]).await;
+
+        let directory_service = ConfigDirectoryService::new_test(
+            "./test-data-8".to_string(),
+            "test1.json".to_string(),
+        );
+
+        let engine = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64.exe.zip".to_string(),
+            "2024-01-20".to_string(),
+            "".to_string(),
+            format!("{}/missing.zip", url),
+        );
+
+        let progress: DownloadProgress = Mutex::new(HashMap::new());
+
+        let result = download_and_extract_engine(
+            &reqwest::Client::new(),
+            &directory_service,
+            &engine,
+            false,
+            &progress,
+        )
+        .await;
+
+        assert!(result.is_err());
+
+        fs::remove_dir_all("./test-data-8").unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_releases_from_stand_in() {
+        let url = test_server::serve(vec![
+            ("/godot", test_data::TEST_DATA.as_bytes().to_vec()),
+            ("/godot-builds", b"[]".to_vec()),
+        ])
+        .await;
+
+        let releases = get_available_releases_from(
+            &reqwest::Client::new(),
+            &[
+                format!("{}/godot", url).as_str(),
+                format!("{}/godot-builds", url).as_str(),
+            ],
+        )
+        .await
+        .unwrap();
+
+        assert!(releases.len() >= 1);
+
+        let filtered = download_service::filter_assets_by_name(&releases, &OsType::Windows64.value());
+        assert!(filtered.len() >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_releases() {
+        let releases = get_available_releases(&reqwest::Client::new())
+            .await
+            .unwrap();
+
+        assert!(releases.len() >= 1)
+    }
+
+    #[tokio::test]
+    async fn test_filter_assets() {
+        let releases = get_available_releases(&reqwest::Client::new())
+            .await
+            .unwrap();
+        let filters = vec![
+            OsType::Windows64.value(),
+            OsType::Windows32.value(),
+            OsType::LinuxArm64.value(),
+            OsType::LinuxArm32.value(),
+            OsType::Linux64.value(),
+            OsType::Linux32.value(),
+            OsType::Mac.value(),
+        ];
+
+        for filter in filters {
+            let filtered = download_service::filter_assets_by_name(&releases, &filter);
+
+            println!("testing '{}'", &filter);
+            assert!(filtered.len() >= 1);
+            assert!(filtered.first().unwrap().name.contains(&filter));
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod download_service;
+pub mod os_type;
+pub mod progress_reporter;
+pub mod release_notes;
@@ -0,0 +1,33 @@
+use std::{collections::HashMap, sync::Mutex};
+
+/// Receives progress updates from long running downloads so callers can display them however they like
+pub trait ProgressReporter: Send + Sync {
+    /// Called whenever more of `name` has been downloaded, with `progress` in percent
+    fn report(&self, name: &str, progress: usize);
+
+    /// Called once `name` has been downloaded and processed
+    fn finish(&self, name: &str);
+}
+
+/// Download progress in percent, keyed by the name of the file being downloaded.
+/// This is what the frontend polls through `poll_download_status_list`.
+pub type DownloadProgress = Mutex<HashMap<String, usize>>;
+
+impl ProgressReporter for DownloadProgress {
+    fn report(&self, name: &str, progress: usize) {
+        self.lock().unwrap().insert(name.to_string(), progress);
+    }
+
+    fn finish(&self, name: &str) {
+        self.lock().unwrap().remove(name);
+    }
+}
+
+/// Discards all progress updates
+pub struct NoProgress;
+
+impl ProgressReporter for NoProgress {
+    fn report(&self, _name: &str, _progress: usize) {}
+
+    fn finish(&self, _name: &str) {}
+}
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    directory::config_directory_service::ConfigDirectoryService,
+    godot_service::export_template_service::release_tag_from_name,
+};
+
+use super::download_service::{self, Release};
+
+/// The notes GitHub shows for a release
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReleaseNotes {
+    #[serde(rename(serialize = "tagName", deserialize = "tagName"))]
+    pub tag_name: String,
+    /// The release notes in Markdown
+    pub body: String,
+    #[serde(rename(serialize = "htmlUrl", deserialize = "htmlUrl"))]
+    pub html_url: String,
+    #[serde(rename(serialize = "publishedAt", deserialize = "publishedAt"))]
+    pub published_at: Option<String>,
+}
+
+/// Finds the release of `version` in `releases`. The version can be a release tag such as `4.2.1-stable`,
+/// a version number such as `4.2.1`, which means the stable release, or an engine name such as `Godot_v4.2.1-stable_win64`.
+pub fn find_release_notes(releases: &[Release], version: &str) -> Option<ReleaseNotes> {
+    let tag = release_tag_from_name(version).unwrap_or(version.to_string());
+    let stable_tag = format!("{}-stable", tag);
+
+    releases
+        .iter()
+        .find(|release| release.tag_name == tag || release.tag_name == stable_tag)
+        .map(|release| ReleaseNotes {
+            tag_name: release.tag_name.clone(),
+            body: release.body.clone().unwrap_or_default(),
+            html_url: release.html_url.clone(),
+            published_at: release.published_at.clone(),
+        })
+}
+
+/// Gets the notes of the release of `version` from the release cache, and only fetches the releases
+/// if the cache doesn't have them
+pub async fn get_release_notes(
+    client: &reqwest::Client,
+    directory_service: &ConfigDirectoryService,
+    version: &str,
+) -> Result<ReleaseNotes, String> {
+    if let Some(notes) = find_release_notes(
+        &download_service::load_release_cache(directory_service),
+        version,
+    ) {
+        return Ok(notes);
+    }
+
+    let releases = download_service::get_cached_releases(client, directory_service)
+        .await
+        .map_err(|error| format!("Could not fetch releases: {}", error))?;
+
+    find_release_notes(&releases, version).ok_or(format!("Did not find a release for {}", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::{
+        directory::config_directory_service::ConfigDirectoryService,
+        fetcher::download_service::{self, Release},
+        test_data,
+    };
+
+    use super::{find_release_notes, get_release_notes};
+
+    #[test]
+    fn test_find_release_notes() {
+        let releases: Vec<Release> = serde_json::from_str(test_data::TEST_DATA).unwrap();
+
+        let notes = find_release_notes(&releases, "4.2.1-stable").unwrap();
+        assert!(notes.tag_name == "4.2.1-stable");
+        assert!(notes.body.starts_with("**Godot 4.2.1**"));
+        assert!(notes.html_url == "https://github.com/godotengine/godot/releases/tag/4.2.1-stable");
+        assert!(notes.published_at == Some("2023-12-12T10:40:48Z".to_string()));
+
+        assert!(
+            find_release_notes(&releases, "Godot_v4.2-stable_mono_win64")
+                .unwrap()
+                .tag_name
+                == "4.2-stable"
+        );
+        assert!(find_release_notes(&releases, "4.1.3").unwrap().tag_name == "4.1.3-stable");
+        assert!(find_release_notes(&releases, "3.0-stable").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_release_notes_offline() {
+        let directory_service = ConfigDirectoryService::new_test(
+            "./test-data-release-notes".to_string(),
+            "config.json".to_string(),
+        );
+        let releases: Vec<Release> = serde_json::from_str(test_data::TEST_DATA).unwrap();
+        download_service::save_release_cache(&directory_service, &releases);
+
+        // Found in the cache, so no releases are fetched
+        let notes = get_release_notes(&reqwest::Client::new(), &directory_service, "4.2.1")
+            .await
+            .unwrap();
+        assert!(notes.tag_name == "4.2.1-stable");
+
+        fs::remove_dir_all("./test-data-release-notes").unwrap();
+    }
+}
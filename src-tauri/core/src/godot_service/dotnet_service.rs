@@ -0,0 +1,121 @@
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use super::godot_engine_version::{parse_major_minor, GodotEngineVersion};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DotnetStatus {
+    #[serde(rename(serialize = "requiredMajorVersion"))]
+    pub required_major_version: Option<u32>,
+    #[serde(rename(serialize = "installedSdks"))]
+    pub installed_sdks: Vec<String>,
+    pub satisfied: bool,
+}
+
+/// Gets the installed .NET SDK versions by running `dotnet --list-sdks`.
+/// Returns an empty list if the dotnet CLI is not installed.
+pub fn get_installed_dotnet_sdks() -> Vec<String> {
+    match Command::new("dotnet").arg("--list-sdks").output() {
+        Ok(output) if output.status.success() => {
+            parse_dotnet_sdk_list(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => vec![],
+    }
+}
+
+/// Parses the output of `dotnet --list-sdks`, where each line looks like `8.0.100 [C:\Program Files\dotnet\sdk]`
+pub fn parse_dotnet_sdk_list(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter(|version| version.chars().next().is_some_and(|c| c.is_ascii_digit()))
+        .map(|version| version.to_string())
+        .collect()
+}
+
+/// Gets the major .NET SDK version a mono engine needs to build C# projects.
+/// Godot 4.0 - 4.3 target .NET 6, Godot 4.4 and later target .NET 8. Godot 3 ships its own Mono runtime.
+pub fn required_dotnet_major_version(engine_version_number: &str) -> Option<u32> {
+    match parse_major_minor(engine_version_number)? {
+        (0..=3, _) => None,
+        (4, 0..=3) => Some(6),
+        _ => Some(8),
+    }
+}
+
+/// Checks the installed .NET SDKs against what the engine requires.
+/// Non-mono engines and engines without a .NET requirement are always satisfied.
+pub fn check_dotnet_for_engine(
+    engine: &GodotEngineVersion,
+    installed_sdks: Vec<String>,
+) -> DotnetStatus {
+    let required_major_version = if engine.is_mono {
+        required_dotnet_major_version(&engine.version_number)
+    } else {
+        None
+    };
+
+    let satisfied = match required_major_version {
+        Some(required) => installed_sdks.iter().any(|sdk| {
+            sdk.split('.')
+                .next()
+                .and_then(|major| major.parse::<u32>().ok())
+                .is_some_and(|major| major >= required)
+        }),
+        None => true,
+    };
+
+    DotnetStatus {
+        required_major_version,
+        installed_sdks,
+        satisfied,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::godot_service::{
+        dotnet_service::{
+            check_dotnet_for_engine, parse_dotnet_sdk_list, required_dotnet_major_version,
+        },
+        godot_engine_version::GodotEngineVersion,
+    };
+
+    #[test]
+    fn test_parse_dotnet_sdk_list() {
+        let output =
+            "6.0.418 [C:\\Program Files\\dotnet\\sdk]\n8.0.100 [C:\\Program Files\\dotnet\\sdk]\n";
+        let sdks = parse_dotnet_sdk_list(output);
+
+        assert!(sdks == vec!["6.0.418", "8.0.100"]);
+        assert!(parse_dotnet_sdk_list("").is_empty());
+    }
+
+    #[test]
+    fn test_required_dotnet_major_version() {
+        assert!(required_dotnet_major_version("3.5.3") == None);
+        assert!(required_dotnet_major_version("4.2.1") == Some(6));
+        assert!(required_dotnet_major_version("4.4") == Some(8));
+    }
+
+    #[test]
+    fn test_check_dotnet_for_engine() {
+        let mono = GodotEngineVersion::new(
+            "Godot_v4.4-stable_mono_win64".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        );
+        let standard = GodotEngineVersion::new(
+            "Godot_v4.4-stable_win64".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        );
+
+        assert!(!check_dotnet_for_engine(&mono, vec!["6.0.418".to_string()]).satisfied);
+        assert!(check_dotnet_for_engine(&mono, vec!["8.0.100".to_string()]).satisfied);
+        assert!(check_dotnet_for_engine(&standard, vec![]).satisfied);
+    }
+}
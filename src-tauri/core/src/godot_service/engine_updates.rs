@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    directory::config_directory_service::{self, ConfigDirectoryService},
+    fetcher::{download_service, progress_reporter::ProgressReporter},
+    project::{project_data::ProjectData, project_service},
+};
+
+use super::{godot_engine_service, godot_engine_version::GodotEngineVersion};
+
+/// A newer release in the same minor series and channel as an installed engine
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EngineUpdate {
+    /// The installed engine that can be updated
+    #[serde(rename(serialize = "engineName", deserialize = "engineName"))]
+    pub engine_name: String,
+    #[serde(rename(serialize = "latestName", deserialize = "latestName"))]
+    pub latest_name: String,
+    #[serde(rename(serialize = "latestVersion", deserialize = "latestVersion"))]
+    pub latest_version: String,
+}
+
+/// Gets the part of an engine name after its version and channel, e.g. `mono_win64` for
+/// `Godot_v4.2.1-stable_mono_win64`. Updates must keep it so a .NET engine is never replaced by a standard one.
+fn build_suffix(version_name: &str) -> &str {
+    version_name
+        .split_once('-')
+        .and_then(|(_, rest)| rest.split_once('_'))
+        .map(|(_, suffix)| suffix)
+        .unwrap_or("")
+}
+
+/// Finds the newest release in `catalog` for every engine in `installed` that has one.
+/// A release only counts as an update if it has the same major and minor version, channel and build suffix.
+pub fn find_engine_updates(
+    installed: &[GodotEngineVersion],
+    catalog: &[GodotEngineVersion],
+) -> Vec<EngineUpdate> {
+    installed
+        .iter()
+        .filter_map(|engine| {
+            let version = engine.release_version()?;
+
+            let latest = catalog
+                .iter()
+                .filter_map(|release| Some((release.release_version()?, release)))
+                .filter(|(release_version, release)| {
+                    release_version.major == version.major
+                        && release_version.minor == version.minor
+                        && release_version.channel == version.channel
+                        && *release_version > version
+                        && build_suffix(&release.version_name) == build_suffix(&engine.version_name)
+                })
+                .max_by_key(|(release_version, _)| *release_version)?
+                .1;
+
+            // The newer release may already be installed next to the old one
+            if installed
+                .iter()
+                .any(|engine| engine.version_name == latest.version_name)
+            {
+                return None;
+            }
+
+            Some(EngineUpdate {
+                engine_name: engine.version_name.clone(),
+                latest_name: latest.version_name.clone(),
+                latest_version: latest.version_number.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Installs the update for the installed engine `engine_name` from `catalog` next to it, keeping the engine's
+/// self-contained mode. The old engine is left in place. Returns the installed update.
+pub async fn upgrade_engine(
+    client: &reqwest::Client,
+    directory_service: &ConfigDirectoryService,
+    engine_name: &str,
+    installed_versions: &[GodotEngineVersion],
+    catalog: &[GodotEngineVersion],
+    progress: &dyn ProgressReporter,
+) -> Result<GodotEngineVersion, String> {
+    godot_engine_service::validate_engine_name(engine_name)?;
+
+    let engine = installed_versions
+        .iter()
+        .find(|engine| engine.version_name == engine_name)
+        .ok_or(format!("{} is not installed", engine_name))?;
+
+    let update = find_engine_updates(std::slice::from_ref(engine), catalog)
+        .pop()
+        .ok_or(format!("There is no update for {}", engine_name))?;
+
+    let latest = catalog
+        .iter()
+        .find(|engine| engine.version_name == update.latest_name)
+        .unwrap();
+
+    download_service::download_and_extract_engine(
+        client,
+        directory_service,
+        latest,
+        engine.self_contained,
+        progress,
+    )
+    .await
+    .map_err(|error| format!("Could not install {}: {}", latest.version_name, error))
+}
+
+/// Moves the projects using the engine `engine_name` to its installed update `upgraded_engine`, except projects
+/// that pinned their engine, and saves them to the config. Returns the names of the moved projects.
+pub fn reassign_to_upgrade(
+    projects: &mut Vec<ProjectData>,
+    engine_name: &str,
+    upgraded_engine: &GodotEngineVersion,
+    directory_service: &ConfigDirectoryService,
+) -> Vec<String> {
+    let reassigned = project_service::reassign_upgraded_engine(
+        projects,
+        engine_name,
+        &upgraded_engine.version_name,
+    );
+    config_directory_service::save_projects_to_config(directory_service, projects);
+    reassigned
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::godot_service::godot_engine_version::GodotEngineVersion;
+
+    use super::find_engine_updates;
+
+    fn engine(name: &str) -> GodotEngineVersion {
+        GodotEngineVersion::new(
+            name.to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_find_engine_updates() {
+        let installed = vec![
+            engine("Godot_v4.2.1-stable_win64"),
+            engine("Godot_v4.2.1-stable_mono_win64"),
+            engine("Godot_v4.3-rc1_win64"),
+            engine("Godot_v4.1.4-stable_win64"),
+        ];
+        let catalog = vec![
+            engine("Godot_v4.3-stable_win64"),
+            engine("Godot_v4.3-rc2_win64"),
+            engine("Godot_v4.2.3-stable_win64"),
+            engine("Godot_v4.2.2-stable_win64"),
+            engine("Godot_v4.2.2-stable_mono_win64"),
+            engine("Godot_v4.2.3-rc1_win64"),
+            engine("Godot_v4.1.4-stable_win64"),
+        ];
+
+        let updates = find_engine_updates(&installed, &catalog);
+
+        assert!(updates.len() == 3);
+        assert!(updates[0].engine_name == "Godot_v4.2.1-stable_win64");
+        assert!(updates[0].latest_name == "Godot_v4.2.3-stable_win64");
+        assert!(updates[0].latest_version == "4.2.3");
+        assert!(updates[1].latest_name == "Godot_v4.2.2-stable_mono_win64");
+        assert!(updates[2].latest_name == "Godot_v4.3-rc2_win64");
+
+        // Installing the update side by side stops it from being offered
+        let mut installed = installed;
+        installed.push(engine("Godot_v4.2.3-stable_win64"));
+        assert!(find_engine_updates(&installed, &catalog).len() == 2);
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    fs,
+    io::Cursor,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use reqwest::header::USER_AGENT;
+use sha2::{Digest, Sha512};
+
+use crate::{
+    directory::config_directory_service::ConfigDirectoryService,
+    fetcher::{download_service, progress_reporter::ProgressReporter},
+};
+
+use super::{godot_engine_service, godot_engine_version::GodotEngineVersion};
+
+const GITHUB_BUILDS_DOWNLOAD_URL: &str =
+    "https://github.com/godotengine/godot-builds/releases/download";
+
+/// Gets the release tag of an engine, e.g. `4.2.1-stable` for `Godot_v4.2.1-stable_win64`
+pub fn release_tag(engine: &GodotEngineVersion) -> Option<String> {
+    release_tag_from_name(&engine.version_name)
+}
+
+/// Gets the release tag out of an engine name, e.g. `4.2.1-stable` for `Godot_v4.2.1-stable_win64`
+pub fn release_tag_from_name(version_name: &str) -> Option<String> {
+    let tag_re = Regex::new(r"Godot_v([^_]+)").unwrap();
+    tag_re
+        .captures(version_name)
+        .map(|captured| captured[1].to_string())
+}
+
+/// Gets the name of the export templates archive for an engine, e.g. `Godot_v4.2.1-stable_mono_export_templates.tpz`
+pub fn export_templates_file_name(engine: &GodotEngineVersion) -> Option<String> {
+    let tag = release_tag(engine)?;
+    let mono = if engine.is_mono { "_mono" } else { "" };
+    Some(format!("Godot_v{}{}_export_templates.tpz", tag, mono))
+}
+
+/// Gets the download url of the export templates for an engine
+pub fn export_templates_url(engine: &GodotEngineVersion) -> Option<String> {
+    let tag = release_tag(engine)?;
+    let file_name = export_templates_file_name(engine)?;
+    Some(format!(
+        "{}/{}/{}",
+        GITHUB_BUILDS_DOWNLOAD_URL, tag, file_name
+    ))
+}
+
+/// Gets the url of the checksum file published alongside the release of an engine
+fn checksums_url(engine: &GodotEngineVersion) -> Option<String> {
+    let tag = release_tag(engine)?;
+    Some(format!(
+        "{}/{}/SHA512-SUMS.txt",
+        GITHUB_BUILDS_DOWNLOAD_URL, tag
+    ))
+}
+
+/// Gets the folder name Godot expects the templates of an engine in, e.g. `4.2.1.stable` or `4.2.1.stable.mono`.
+/// This matches the contents of the `version.txt` file inside the templates archive.
+pub fn export_templates_version(engine: &GodotEngineVersion) -> Option<String> {
+    let tag = release_tag(engine)?;
+    let mono = if engine.is_mono { ".mono" } else { "" };
+    Some(format!("{}{}", tag.replace("-", "."), mono))
+}
+
+/// Gets the folder the export templates of an engine are installed to.
+/// Self-contained engines keep them in `editor_data/export_templates` beside the executable.
+pub fn export_templates_path(engine: &GodotEngineVersion) -> Option<PathBuf> {
+    let version = export_templates_version(engine)?;
+
+    let mut path = if engine.is_self_contained() {
+        let mut path = engine.executable_directory();
+        path.push("editor_data");
+        path
+    } else {
+        godot_engine_service::godot_data_path()
+    };
+
+    path.push("export_templates");
+    path.push(version);
+    Some(path)
+}
+
+/// Checks if the export templates for an engine are installed by looking for their `version.txt`
+pub fn are_export_templates_installed(engine: &GodotEngineVersion) -> bool {
+    match export_templates_path(engine) {
+        Some(path) => path.join("version.txt").exists(),
+        None => false,
+    }
+}
+
+/// Finds the expected SHA-512 hash of `file_name` in the contents of a `SHA512-SUMS.txt` file
+pub fn find_checksum(checksums: &str, file_name: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        let name = parts.next()?;
+        if name.trim_start_matches('*') == file_name {
+            Some(hash.to_lowercase())
+        } else {
+            None
+        }
+    })
+}
+
+/// Checks that the templates archive matches the published checksum
+pub fn verify_checksum(archive: &[u8], expected: &str) -> Result<(), String> {
+    let actual = format!("{:x}", Sha512::digest(archive));
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "Checksum mismatch for export templates, expected {} but got {}",
+            expected, actual
+        ))
+    }
+}
+
+/// Checks that extracted templates were built for the engine by comparing their `version.txt`
+pub fn verify_extracted_templates(path: &Path, expected_version: &str) -> Result<(), String> {
+    let version_file = path.join("version.txt");
+    let version = fs::read_to_string(&version_file)
+        .map_err(|_| format!("Could not read {}", version_file.display()))?;
+
+    if version.trim() == expected_version {
+        Ok(())
+    } else {
+        Err(format!(
+            "Export templates are for version {} but {} was expected",
+            version.trim(),
+            expected_version
+        ))
+    }
+}
+
+async fn download_checksums(
+    client: &reqwest::Client,
+    engine: &GodotEngineVersion,
+) -> Result<String, String> {
+    let url = checksums_url(engine).ok_or("Could not find release tag")?;
+    let response = client
+        .get(&url)
+        .header(USER_AGENT, "My Rust Program 1.0")
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|error| format!("Could not download {}: {}", url, error))?;
+
+    response
+        .text()
+        .await
+        .map_err(|error| format!("Could not read {}: {}", url, error))
+}
+
+/// Downloads the export templates for an installed engine, verifies them and extracts them into
+/// the folder Godot expects them in. Returns the folder the templates were installed to.
+/// Fails if the published checksums can't be fetched, since the download can't be verified then.
+/// The downloaded archive is removed whether the installation succeeds or not.
+pub async fn install_export_templates(
+    client: &reqwest::Client,
+    directory_service: &ConfigDirectoryService,
+    engine: &GodotEngineVersion,
+    progress: &dyn ProgressReporter,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let file_name = export_templates_file_name(engine).ok_or("Could not find release tag")?;
+
+    let download_path = directory_service.download_cache_path();
+    fs::create_dir_all(&download_path)?;
+    let file_path = download_path.join(&file_name);
+
+    let installed =
+        download_and_extract_templates(client, engine, &file_path, &file_name, progress).await;
+
+    if file_path.exists() {
+        fs::remove_file(&file_path)?;
+    }
+
+    installed
+}
+
+async fn download_and_extract_templates(
+    client: &reqwest::Client,
+    engine: &GodotEngineVersion,
+    file_path: &Path,
+    file_name: &str,
+    progress: &dyn ProgressReporter,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let url = export_templates_url(engine).ok_or("Could not find release tag")?;
+    let version = export_templates_version(engine).ok_or("Could not find release tag")?;
+    let target_dir = export_templates_path(engine).ok_or("Could not find release tag")?;
+
+    let archive =
+        download_service::download_url(client, &url, file_path, file_name, progress).await?;
+
+    progress.finish(file_name);
+
+    let checksums = download_checksums(client, engine)
+        .await
+        .map_err(|error| format!("{}, the export templates can't be verified", error))?;
+    let expected = find_checksum(&checksums, file_name)
+        .ok_or(format!("The published checksums don't list {}", file_name))?;
+    verify_checksum(&archive, &expected)?;
+
+    if target_dir.exists() {
+        fs::remove_dir_all(&target_dir)?;
+    }
+    fs::create_dir_all(&target_dir)?;
+
+    // The archive has a single top level `templates` folder, strip it so the files land in the version folder
+    let extracted = zip_extract::extract(Cursor::new(archive), &target_dir, true)
+        .map_err(|error| error.to_string())
+        .and_then(|_| verify_extracted_templates(&target_dir, &version));
+
+    if let Err(error) = extracted {
+        fs::remove_dir_all(&target_dir)?;
+        return Err(error.into());
+    }
+
+    Ok(target_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use sha2::{Digest, Sha512};
+
+    use crate::godot_service::{
+        export_template_service::{
+            export_templates_file_name, export_templates_url, export_templates_version,
+            find_checksum, verify_checksum, verify_extracted_templates,
+        },
+        godot_engine_version::GodotEngineVersion,
+    };
+
+    fn engine(name: &str) -> GodotEngineVersion {
+        GodotEngineVersion::new(
+            name.to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_export_templates_names() {
+        let standard = engine("Godot_v4.2.1-stable_win64");
+        let mono = engine("Godot_v4.3-rc1_mono_win64");
+
+        assert!(
+            export_templates_file_name(&standard)
+                == Some("Godot_v4.2.1-stable_export_templates.tpz".to_string())
+        );
+        assert!(
+            export_templates_file_name(&mono)
+                == Some("Godot_v4.3-rc1_mono_export_templates.tpz".to_string())
+        );
+        assert!(export_templates_version(&standard) == Some("4.2.1.stable".to_string()));
+        assert!(export_templates_version(&mono) == Some("4.3.rc1.mono".to_string()));
+        assert!(
+            export_templates_url(&standard)
+                == Some("https://github.com/godotengine/godot-builds/releases/download/4.2.1-stable/Godot_v4.2.1-stable_export_templates.tpz".to_string())
+        );
+        assert!(export_templates_file_name(&engine("not an engine")).is_none());
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let archive = b"templates".to_vec();
+        let hash = format!("{:x}", Sha512::digest(&archive));
+        let checksums = format!(
+            "{}  Godot_v4.2.1-stable_export_templates.tpz\nabc  Godot_v4.2.1-stable_win64.exe.zip\n",
+            hash
+        );
+
+        let expected = find_checksum(&checksums, "Godot_v4.2.1-stable_export_templates.tpz");
+
+        assert!(expected == Some(hash));
+        assert!(verify_checksum(&archive, &expected.unwrap()).is_ok());
+        assert!(verify_checksum(&archive, "abc").is_err());
+        assert!(find_checksum(&checksums, "Godot_v4.2.1-stable_linux.x86_64.zip").is_none());
+    }
+
+    #[test]
+    fn test_verify_extracted_templates() {
+        let path = ".\\test-data-templates";
+        fs::create_dir_all(path).unwrap();
+        fs::write(format!("{}/version.txt", path), "4.2.1.stable\n").unwrap();
+
+        assert!(verify_extracted_templates(path.as_ref(), "4.2.1.stable").is_ok());
+        assert!(verify_extracted_templates(path.as_ref(), "4.2.1.stable.mono").is_err());
+
+        fs::remove_dir_all(path).unwrap();
+    }
+}
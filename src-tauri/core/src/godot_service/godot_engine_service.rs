@@ -0,0 +1,706 @@
+// grab all engine versions from github
+// list all engines
+// download one and unzip
+// save as a downloaded engine
+
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::Local;
+use directories::BaseDirs;
+
+use crate::{
+    directory::config_directory_service::{self, ConfigDirectoryService},
+    fetcher::download_service::Asset,
+    project::{project_data::ProjectData, project_service},
+};
+
+use super::{export_template_service, godot_engine_version::GodotEngineVersion};
+
+pub fn from_asset(asset: Asset) -> GodotEngineVersion {
+    let mut name = asset.name;
+    name = name.replace(".zip", "");
+    name = name.replace(".exe", "");
+
+    GodotEngineVersion::new(
+        name,
+        asset.created_at,
+        "".to_string(),
+        asset.browser_download_url,
+    )
+}
+
+pub fn get_installed_godot_versions(
+    directory_service: &ConfigDirectoryService,
+) -> Vec<GodotEngineVersion> {
+    let path = directory_service.engine_storage_path();
+
+    let paths = fs::read_dir(path).unwrap();
+
+    let mut engine_versions: Vec<GodotEngineVersion> = vec![];
+
+    for path in paths {
+        let p = path.unwrap().path();
+
+        if p.is_dir() {
+            let name = p.file_name().unwrap().to_string_lossy().to_string();
+
+            let path = p.display().to_string();
+
+            let mut engine_version = GodotEngineVersion::new(
+                name.to_string(),
+                "2024-08-12".to_string(),
+                path,
+                "".to_string(),
+            );
+            engine_version.self_contained = engine_version.is_self_contained();
+            engine_version.export_templates_installed =
+                export_template_service::are_export_templates_installed(&engine_version);
+
+            engine_versions.push(engine_version)
+        }
+    }
+
+    engine_versions
+}
+
+/// Gets the names of the projects assigned to the engine `engine_name`
+pub fn engine_dependents(engine_name: &str, projects: &[ProjectData]) -> Vec<String> {
+    projects
+        .iter()
+        .filter(|project| project.engine_version == engine_name)
+        .map(|project| project.project_name.clone())
+        .collect()
+}
+
+/// Checks that an engine name, e.g. from the frontend or a release asset, can be used as a folder name
+/// inside the engines folder without pointing anywhere else
+pub fn validate_engine_name(engine_name: &str) -> Result<(), String> {
+    let is_valid = !engine_name.trim().is_empty()
+        && engine_name != "."
+        && engine_name != ".."
+        && !engine_name.contains(['/', '\\', ':', '\0']);
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(format!("{} is not a valid engine name", engine_name))
+    }
+}
+
+/// Where an installed engine lives, as far as removing it is concerned
+#[derive(Debug, PartialEq)]
+pub enum EngineLocation {
+    /// A folder inside the engines folder that the manager owns and may delete
+    Managed(PathBuf),
+    /// A link inside the engines folder to an engine stored elsewhere. Only the link may be removed.
+    Linked(PathBuf),
+}
+
+/// Resolves the installation path of an engine, following symlinks, and makes sure it is the engine's own folder
+/// directly inside the engines folder, so a corrupted entry can't point at another engine or a nested folder
+pub fn engine_location(
+    godot_engine_version: &GodotEngineVersion,
+    directory_service: &ConfigDirectoryService,
+) -> Result<EngineLocation, String> {
+    validate_engine_name(&godot_engine_version.version_name)?;
+
+    if godot_engine_version.path.is_empty() {
+        return Err(format!(
+            "{} has no installation path",
+            godot_engine_version.version_name
+        ));
+    }
+
+    let storage_path = fs::canonicalize(directory_service.engine_storage_path())
+        .map_err(|error| format!("Could not resolve the engines folder: {}", error))?;
+    let path = Path::new(&godot_engine_version.path);
+    let outside_error = || {
+        format!(
+            "{} is not installed inside {}, refusing to delete it",
+            godot_engine_version.version_name,
+            storage_path.display()
+        )
+    };
+
+    let metadata = fs::symlink_metadata(path).map_err(|error| {
+        format!(
+            "{} is not installed: {}",
+            godot_engine_version.version_name, error
+        )
+    })?;
+
+    let is_engine_folder = |path: &Path| {
+        path.parent() == Some(storage_path.as_path())
+            && path.file_name() == Some(OsStr::new(&godot_engine_version.version_name))
+    };
+
+    if metadata.file_type().is_symlink() {
+        let parent = path
+            .parent()
+            .and_then(|parent| fs::canonicalize(parent).ok())
+            .ok_or_else(outside_error)?;
+        let link = parent.join(path.file_name().ok_or_else(outside_error)?);
+        if !is_engine_folder(&link) {
+            return Err(outside_error());
+        }
+
+        return Ok(EngineLocation::Linked(link));
+    }
+
+    let canonical_path = fs::canonicalize(path)
+        .map_err(|error| format!("Could not resolve {}: {}", godot_engine_version.path, error))?;
+
+    if !is_engine_folder(&canonical_path) {
+        return Err(outside_error());
+    }
+
+    Ok(EngineLocation::Managed(canonical_path))
+}
+
+/// Deletes an installed engine. The engine folder is first renamed into the trash folder and then deleted,
+/// so a deletion that fails part way never leaves a half-deleted engine in the engines folder.
+/// Once the rename succeeded the engine counts as removed, whatever is left in the trash is deleted by the next
+/// removal. Engines linked from elsewhere are only unlinked.
+pub fn remove_installed_version(
+    godot_engine_version: &GodotEngineVersion,
+    directory_service: &ConfigDirectoryService,
+) -> Result<(), String> {
+    let path = match engine_location(godot_engine_version, directory_service)? {
+        EngineLocation::Managed(path) => path,
+        EngineLocation::Linked(link) => {
+            // Directory symlinks are files on Unix but directories on Windows
+            return fs::remove_file(&link)
+                .or_else(|_| fs::remove_dir(&link))
+                .map_err(|error| {
+                    format!(
+                        "Could not unlink {}: {}",
+                        godot_engine_version.version_name, error
+                    )
+                });
+        }
+    };
+
+    empty_trash(directory_service);
+
+    let trash_path = directory_service.trash_path().join(format!(
+        "{}_{}",
+        godot_engine_version.version_name,
+        Local::now().timestamp_millis()
+    ));
+    fs::create_dir_all(directory_service.trash_path())
+        .map_err(|error| format!("Could not create trash folder: {}", error))?;
+
+    fs::rename(&path, &trash_path).map_err(|error| {
+        format!(
+            "Could not move {} to the trash: {}",
+            godot_engine_version.version_name, error
+        )
+    })?;
+
+    fs::remove_dir_all(&trash_path)
+        .map_err(|error| {
+            println!(
+                "{} was moved to {} but could not be deleted: {}",
+                godot_engine_version.version_name,
+                trash_path.display(),
+                error
+            )
+        })
+        .ok();
+
+    Ok(())
+}
+
+/// The outcome of [`remove_engine`]
+pub struct EngineRemoval {
+    /// Whether the engine was removed. It is kept if projects depend on it and removal was not forced.
+    pub removed: bool,
+    /// The projects that were assigned to the engine
+    pub dependents: Vec<String>,
+}
+
+/// Removes the installed engine `engine_name`. If projects are assigned to it nothing is removed unless `force`
+/// is set, in which case they are assigned `reassign_to`, or marked as having an invalid engine if it isn't given.
+/// The changed projects are saved to the config.
+pub fn remove_engine(
+    engine_name: &str,
+    installed_versions: &[GodotEngineVersion],
+    projects: &mut Vec<ProjectData>,
+    force: bool,
+    reassign_to: Option<&str>,
+    directory_service: &ConfigDirectoryService,
+) -> Result<EngineRemoval, String> {
+    validate_engine_name(engine_name)?;
+
+    let engine = installed_versions
+        .iter()
+        .find(|engine| engine.version_name == engine_name)
+        .ok_or(format!("{} is not installed", engine_name))?;
+
+    if let Some(replacement) = reassign_to {
+        if replacement == engine_name
+            || !installed_versions
+                .iter()
+                .any(|engine| engine.version_name == replacement)
+        {
+            return Err(format!("{} cannot replace {}", replacement, engine_name));
+        }
+    }
+
+    let dependents = engine_dependents(engine_name, projects);
+    if !dependents.is_empty() && !force {
+        return Ok(EngineRemoval {
+            removed: false,
+            dependents,
+        });
+    }
+
+    remove_installed_version(engine, directory_service)?;
+
+    project_service::reassign_engine(projects, engine_name, reassign_to);
+    config_directory_service::save_projects_to_config(directory_service, projects);
+
+    Ok(EngineRemoval {
+        removed: true,
+        dependents,
+    })
+}
+
+/// Deletes everything left in the trash folder by removals that failed part way
+pub fn empty_trash(directory_service: &ConfigDirectoryService) {
+    let Ok(entries) = fs::read_dir(directory_service.trash_path()) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        fs::remove_dir_all(entry.path())
+            .map_err(|error| println!("Could not empty trash: {}", error))
+            .ok();
+    }
+}
+
+/// Gets Godot's own user data folder, which holds `export_templates` for engines that are not self-contained
+pub fn godot_data_path() -> PathBuf {
+    let mut path = BaseDirs::new().unwrap().data_dir().to_path_buf();
+    if cfg!(target_os = "linux") {
+        path.push("godot");
+    } else {
+        path.push("Godot");
+    }
+    path
+}
+
+/// Gets Godot's own config folder, which holds the editor settings for engines that are not self-contained
+pub fn godot_config_path() -> PathBuf {
+    let mut path = BaseDirs::new().unwrap().config_dir().to_path_buf();
+    if cfg!(target_os = "linux") {
+        path.push("godot");
+    } else {
+        path.push("Godot");
+    }
+    path
+}
+
+/// Gets the editor settings file name an engine uses. Godot 4.3 and later version the file per minor release.
+pub fn editor_settings_file_name(godot_engine_version: &GodotEngineVersion) -> Option<String> {
+    let (major, minor) = godot_engine_version.major_minor()?;
+    if major > 4 || (major == 4 && minor >= 3) {
+        Some(format!("editor_settings-{}.{}.tres", major, minor))
+    } else {
+        Some(format!("editor_settings-{}.tres", major))
+    }
+}
+
+/// Switches an installed engine in or out of self-contained mode by creating or removing the `._sc_` marker
+/// next to its executable. If `migrate_settings` is set the editor settings are copied over from the mode being left.
+/// Settings are never copied over an existing file.
+pub fn set_self_contained(
+    godot_engine_version: &GodotEngineVersion,
+    self_contained: bool,
+    migrate_settings: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let executable_directory = godot_engine_version.executable_directory();
+    if !executable_directory.exists() {
+        return Err(format!("{} is not installed", godot_engine_version.version_name).into());
+    }
+
+    let editor_data_path = executable_directory.join("editor_data");
+    let settings_file_name = editor_settings_file_name(godot_engine_version);
+
+    if self_contained {
+        fs::File::create(executable_directory.join("._sc_"))?;
+        fs::create_dir_all(&editor_data_path)?;
+    } else {
+        for marker in ["._sc_", "_sc_"] {
+            let marker_path = executable_directory.join(marker);
+            if marker_path.exists() {
+                fs::remove_file(marker_path)?;
+            }
+        }
+    }
+
+    if let (true, Some(file_name)) = (migrate_settings, settings_file_name) {
+        let (from, to) = if self_contained {
+            (
+                godot_config_path().join(&file_name),
+                editor_data_path.join(&file_name),
+            )
+        } else {
+            (
+                editor_data_path.join(&file_name),
+                godot_config_path().join(&file_name),
+            )
+        };
+
+        if from.exists() && !to.exists() {
+            fs::create_dir_all(to.parent().unwrap())?;
+            fs::copy(from, to)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        directory::config_directory_service::{self, ConfigDirectoryService},
+        fetcher::{download_service, progress_reporter::NoProgress},
+        godot_service::{
+            godot_engine_service::{
+                editor_settings_file_name, engine_dependents, engine_location,
+                get_installed_godot_versions, remove_engine, remove_installed_version,
+                set_self_contained, validate_engine_name, EngineLocation,
+            },
+            godot_engine_version::GodotEngineVersion,
+        },
+        project::project_data::ProjectData,
+        test_server,
+    };
+    use chrono::Local;
+    use std::{fs, path::PathBuf};
+
+    #[test]
+    fn test_editor_settings_file_name() {
+        let engine = |name: &str| {
+            GodotEngineVersion::new(
+                name.to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+            )
+        };
+
+        assert!(
+            editor_settings_file_name(&engine("Godot_v3.5.3-stable_win64"))
+                == Some("editor_settings-3.tres".to_string())
+        );
+        assert!(
+            editor_settings_file_name(&engine("Godot_v4.2.1-stable_win64"))
+                == Some("editor_settings-4.tres".to_string())
+        );
+        assert!(
+            editor_settings_file_name(&engine("Godot_v4.3-stable_win64"))
+                == Some("editor_settings-4.3.tres".to_string())
+        );
+    }
+
+    #[test]
+    fn test_set_self_contained() {
+        let path = "./test-data-sc/Godot_v4.2.1-stable_win64";
+        fs::create_dir_all(path).unwrap();
+
+        let engine = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+            path.to_string(),
+            "".to_string(),
+        );
+
+        assert!(!engine.is_self_contained());
+
+        set_self_contained(&engine, true, false).unwrap();
+        assert!(engine.is_self_contained());
+        assert!(engine.executable_directory().join("editor_data").exists());
+
+        set_self_contained(&engine, false, false).unwrap();
+        assert!(!engine.is_self_contained());
+
+        fs::remove_dir_all("./test-data-sc").unwrap();
+    }
+
+    #[test]
+    fn test_remove_installed_version() {
+        let directory_service = ConfigDirectoryService::new_test(
+            "./test-data-remove-engine".to_string(),
+            "config.json".to_string(),
+        );
+        let path = directory_service.engine_version_path("Godot_v4.2.1-stable_win64");
+        fs::create_dir_all(path.join("GodotSharp")).unwrap();
+        fs::write(path.join("Godot_v4.2.1-stable_win64.exe"), "").unwrap();
+        fs::create_dir_all(directory_service.trash_path().join("leftover")).unwrap();
+
+        let engine = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+            path.to_string_lossy().to_string(),
+            "".to_string(),
+        );
+        let projects = vec![ProjectData::new(
+            "./my_game/project.godot".to_string(),
+            "Godot_v4.2.1-stable_win64".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        )];
+
+        assert!(engine_dependents("Godot_v4.2.1-stable_win64", &projects) == vec!["my_game"]);
+        assert!(engine_dependents("Godot_v4.1.3-stable_win64", &projects).is_empty());
+
+        remove_installed_version(&engine, &directory_service).unwrap();
+
+        assert!(!path.exists());
+        assert!(fs::read_dir(directory_service.trash_path())
+            .unwrap()
+            .next()
+            .is_none());
+        assert!(remove_installed_version(&engine, &directory_service).is_err());
+
+        fs::remove_dir_all("./test-data-remove-engine").unwrap();
+    }
+
+    #[test]
+    fn test_remove_engine() {
+        let directory_service = ConfigDirectoryService::new_test(
+            "./test-data-remove-engine-projects".to_string(),
+            "config.json".to_string(),
+        );
+        for name in ["Godot_v4.2.1-stable_win64", "Godot_v4.3-stable_win64"] {
+            fs::create_dir_all(directory_service.engine_version_path(name)).unwrap();
+        }
+        let installed_versions = get_installed_godot_versions(&directory_service);
+        let mut projects = vec![ProjectData::new(
+            "./my_game/project.godot".to_string(),
+            "Godot_v4.2.1-stable_win64".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        )];
+
+        assert!(remove_engine(
+            "Godot_v4.2.1-stable_win64",
+            &installed_versions,
+            &mut projects,
+            true,
+            Some("Godot_v4.2.1-stable_win64"),
+            &directory_service,
+        )
+        .is_err());
+
+        let kept = remove_engine(
+            "Godot_v4.2.1-stable_win64",
+            &installed_versions,
+            &mut projects,
+            false,
+            None,
+            &directory_service,
+        )
+        .unwrap();
+        assert!(!kept.removed);
+        assert!(kept.dependents == vec!["my_game"]);
+        assert!(directory_service
+            .engine_version_path("Godot_v4.2.1-stable_win64")
+            .exists());
+
+        let removed = remove_engine(
+            "Godot_v4.2.1-stable_win64",
+            &installed_versions,
+            &mut projects,
+            true,
+            Some("Godot_v4.3-stable_win64"),
+            &directory_service,
+        )
+        .unwrap();
+        assert!(removed.removed);
+        assert!(!directory_service
+            .engine_version_path("Godot_v4.2.1-stable_win64")
+            .exists());
+        assert!(projects[0].engine_version == "Godot_v4.3-stable_win64");
+        assert!(
+            config_directory_service::get_project_config(&directory_service).tracked_projects[0]
+                .engine_version
+                == "Godot_v4.3-stable_win64"
+        );
+
+        fs::remove_dir_all("./test-data-remove-engine-projects").unwrap();
+    }
+
+    #[test]
+    fn test_validate_engine_name() {
+        assert!(validate_engine_name("Godot_v4.2.1-stable_win64").is_ok());
+        assert!(validate_engine_name("Godot_v4.2.1-stable_mono_linux.x86_64").is_ok());
+        assert!(validate_engine_name("").is_err());
+        assert!(validate_engine_name("..").is_err());
+        assert!(validate_engine_name("../config").is_err());
+        assert!(validate_engine_name("..\\..\\Windows").is_err());
+        assert!(validate_engine_name("C:").is_err());
+    }
+
+    #[test]
+    fn test_engine_location_outside_storage() {
+        let directory_service = ConfigDirectoryService::new_test(
+            "./test-data-engine-location".to_string(),
+            "config.json".to_string(),
+        );
+        fs::create_dir_all(directory_service.engine_storage_path()).unwrap();
+        fs::create_dir_all("./test-data-engine-location/Godot_v4.2.1-stable_win64").unwrap();
+
+        let engine = |path: &str| {
+            GodotEngineVersion::new(
+                "Godot_v4.2.1-stable_win64".to_string(),
+                "".to_string(),
+                path.to_string(),
+                "".to_string(),
+            )
+        };
+
+        // Contains the engine name, which was all the old check looked at
+        let outside = engine("./test-data-engine-location/Godot_v4.2.1-stable_win64");
+        assert!(engine_location(&outside, &directory_service).is_err());
+        assert!(remove_installed_version(&outside, &directory_service).is_err());
+        assert!(fs::metadata("./test-data-engine-location/Godot_v4.2.1-stable_win64").is_ok());
+
+        let traversal = engine("./test-data-engine-location/engines/../Godot_v4.2.1-stable_win64");
+        assert!(engine_location(&traversal, &directory_service).is_err());
+
+        let storage = engine("./test-data-engine-location/engines");
+        assert!(engine_location(&storage, &directory_service).is_err());
+
+        let missing = engine("./test-data-engine-location/engines/Godot_v4.2.1-stable_win64");
+        assert!(engine_location(&missing, &directory_service).is_err());
+
+        // A corrupted entry pointing at another engine or a folder nested inside one
+        fs::create_dir_all(
+            "./test-data-engine-location/engines/Godot_v4.1.3-stable_win64/editor_data",
+        )
+        .unwrap();
+        let other = engine("./test-data-engine-location/engines/Godot_v4.1.3-stable_win64");
+        assert!(engine_location(&other, &directory_service).is_err());
+        let nested =
+            engine("./test-data-engine-location/engines/Godot_v4.1.3-stable_win64/editor_data");
+        assert!(engine_location(&nested, &directory_service).is_err());
+        assert!(remove_installed_version(&other, &directory_service).is_err());
+        assert!(
+            fs::metadata("./test-data-engine-location/engines/Godot_v4.1.3-stable_win64").is_ok()
+        );
+
+        fs::create_dir_all("./test-data-engine-location/engines/Godot_v4.2.1-stable_win64")
+            .unwrap();
+        assert!(matches!(
+            engine_location(&missing, &directory_service),
+            Ok(EngineLocation::Managed(_))
+        ));
+
+        let mut bad_name = engine("./test-data-engine-location/engines/..");
+        bad_name.version_name = "..".to_string();
+        assert!(engine_location(&bad_name, &directory_service).is_err());
+
+        fs::remove_dir_all("./test-data-engine-location").unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_engine_location_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let directory_service = ConfigDirectoryService::new_test(
+            "./test-data-engine-symlinks".to_string(),
+            "config.json".to_string(),
+        );
+        let external = PathBuf::from("./test-data-engine-symlinks/external");
+        fs::create_dir_all(directory_service.engine_storage_path()).unwrap();
+        fs::create_dir_all(&external).unwrap();
+        fs::write(external.join("Godot_v4.2.1-stable_linux.x86_64"), "").unwrap();
+
+        let link = directory_service.engine_version_path("Godot_v4.2.1-stable_linux.x86_64");
+        symlink(fs::canonicalize(&external).unwrap(), &link).unwrap();
+
+        let linked = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_linux.x86_64".to_string(),
+            "".to_string(),
+            link.to_string_lossy().to_string(),
+            "".to_string(),
+        );
+        assert!(matches!(
+            engine_location(&linked, &directory_service),
+            Ok(EngineLocation::Linked(_))
+        ));
+
+        remove_installed_version(&linked, &directory_service).unwrap();
+        assert!(fs::symlink_metadata(&link).is_err());
+        assert!(external.join("Godot_v4.2.1-stable_linux.x86_64").exists());
+
+        // A path that only reaches outside through a symlinked folder is resolved and refused
+        let escape = directory_service.engine_storage_path().join("escape");
+        symlink(
+            fs::canonicalize("./test-data-engine-symlinks").unwrap(),
+            &escape,
+        )
+        .unwrap();
+        let through_link = GodotEngineVersion::new(
+            "external".to_string(),
+            "".to_string(),
+            escape.join("external").to_string_lossy().to_string(),
+            "".to_string(),
+        );
+        assert!(engine_location(&through_link, &directory_service).is_err());
+        assert!(external.exists());
+
+        fs::remove_dir_all("./test-data-engine-symlinks").unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_find_godot_version() {
+        let archive = test_server::build_zip(vec![
+            ("Godot_v4.2.1-stable_win64.exe", b"engine"),
+            ("Godot_v4.2.1-stable_win64_console.exe", b"console"),
+        ]);
+        let url = test_server::serve(vec![("/Godot_v4.2.1-stable_win64.exe.zip", archive)]).await;
+
+        let directory_service =
+            ConfigDirectoryService::new_test("./test-data-7".to_string(), "test1.json".to_string());
+
+        let engine = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64.exe.zip".to_string(),
+            "2024-01-20".to_string(),
+            "".to_string(),
+            format!("{}/Godot_v4.2.1-stable_win64.exe.zip", url),
+        );
+
+        let updated_engine = download_service::download_and_extract_engine(
+            &reqwest::Client::new(),
+            &directory_service,
+            &engine,
+            true,
+            &NoProgress,
+        )
+        .await
+        .unwrap();
+
+        assert!(updated_engine.version_name == "Godot_v4.2.1-stable_win64");
+        assert!(updated_engine.version_number == "4.2.1");
+        assert!(!updated_engine.path.is_empty());
+
+        let versions = get_installed_godot_versions(&directory_service);
+
+        assert!(versions.len() == 1);
+        assert!(versions[0].version_name == "Godot_v4.2.1-stable_win64");
+        assert!(versions[0].self_contained);
+
+        fs::remove_dir_all("./test-data-7").unwrap();
+    }
+}
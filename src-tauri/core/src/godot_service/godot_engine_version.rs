@@ -0,0 +1,249 @@
+use core::fmt;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GodotEngineVersion {
+    #[serde(rename(serialize = "engineName", deserialize = "engineName"))]
+    pub version_name: String,
+    #[serde(rename(serialize = "engineVersion", deserialize = "engineVersion"))]
+    pub version_number: String,
+    #[serde(rename(serialize = "updatedAt", deserialize = "updatedAt"))]
+    pub updated_at: String,
+    #[serde(rename(serialize = "installationPath", deserialize = "installationPath"))]
+    pub path: String,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub download_url: String,
+    pub executable_path: String,
+    pub console_executable_path: String,
+    #[serde(rename(serialize = "isMono", deserialize = "isMono"), default)]
+    pub is_mono: bool,
+    #[serde(
+        rename(
+            serialize = "exportTemplatesInstalled",
+            deserialize = "exportTemplatesInstalled"
+        ),
+        default
+    )]
+    pub export_templates_installed: bool,
+    #[serde(
+        rename(serialize = "selfContained", deserialize = "selfContained"),
+        default
+    )]
+    pub self_contained: bool,
+}
+
+impl GodotEngineVersion {
+    pub fn new(
+        version_name: String,
+        updated_at: String,
+        path: String,
+        download_url: String,
+    ) -> GodotEngineVersion {
+        let version_number = version_number_from_name(&version_name);
+
+        let is_mono = version_name.contains("_mono_");
+
+        let mut name = version_name.clone();
+        name = name.replace(".zip", "");
+        name = name.replace(".exe", "");
+
+        let path_buf = PathBuf::from(&path);
+
+        let executable_path = find_executable_path(&path_buf, &name, ".exe");
+        let console_executable_path = find_executable_path(&path_buf, &name, "_console.exe");
+
+        if path_buf.exists() && !&executable_path.exists() {
+            println!("executable_path does not exist: {:?}", &executable_path);
+        }
+
+        if path_buf.exists() && !&console_executable_path.exists() {
+            println!(
+                "console_executable_path does not exist: {:?}",
+                &console_executable_path
+            );
+        }
+
+        GodotEngineVersion {
+            version_name: name,
+            version_number: version_number,
+            path: path,
+            updated_at: updated_at,
+            download_url: download_url,
+            executable_path: executable_path.to_str().unwrap().to_string(),
+            console_executable_path: console_executable_path.to_str().unwrap().to_string(),
+            is_mono,
+            export_templates_installed: false,
+            self_contained: false,
+        }
+    }
+
+    /// Gets the major and minor version of the engine, e.g. `(4, 2)` for `4.2.1`
+    pub fn major_minor(&self) -> Option<(u32, u32)> {
+        parse_major_minor(&self.version_number)
+    }
+
+    /// Parses the version, channel and build of the engine from its name
+    pub fn release_version(&self) -> Option<ReleaseVersion> {
+        parse_release_version(&self.version_name)
+    }
+
+    /// Gets the folder the engine executable lives in
+    pub fn executable_directory(&self) -> PathBuf {
+        match Path::new(&self.executable_path).parent() {
+            Some(parent) => parent.to_path_buf(),
+            None => PathBuf::from(&self.path),
+        }
+    }
+
+    /// An engine is self-contained if a `._sc_` or `_sc_` file sits next to its executable.
+    /// Self-contained engines keep their editor data in an `editor_data` folder beside the executable.
+    pub fn is_self_contained(&self) -> bool {
+        let directory = self.executable_directory();
+        directory.join("._sc_").exists() || directory.join("_sc_").exists()
+    }
+}
+
+/// Gets the version number from an engine name, e.g. `4.2.1` from `Godot_v4.2.1-stable_win64`.
+/// Returns the whole name if it contains no version.
+pub fn version_number_from_name(version_name: &str) -> String {
+    let version_re = Regex::new(r"v[\d+.+]+-").unwrap();
+
+    match version_re.find(version_name) {
+        Some(result) => {
+            let str = result.as_str();
+            str[1..(str.len() - 1)].to_string()
+        }
+        None => version_name.to_string(),
+    }
+}
+
+/// Parses the major and minor version out of a version number such as `4.2.1` or `4.3`
+pub fn parse_major_minor(version_number: &str) -> Option<(u32, u32)> {
+    let mut parts = version_number.split('.');
+    let major = parts.next()?.parse::<u32>().ok()?;
+    let minor = parts.next().unwrap_or("0").parse::<u32>().unwrap_or(0);
+    Some((major, minor))
+}
+
+/// The release channel of an engine build, ordered from least to most stable
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReleaseChannel {
+    Dev,
+    Alpha,
+    Beta,
+    Rc,
+    Stable,
+}
+
+impl ReleaseChannel {
+    pub fn from_name(channel: &str) -> Option<ReleaseChannel> {
+        match channel {
+            "dev" => Some(ReleaseChannel::Dev),
+            "alpha" => Some(ReleaseChannel::Alpha),
+            "beta" => Some(ReleaseChannel::Beta),
+            "rc" => Some(ReleaseChannel::Rc),
+            "stable" => Some(ReleaseChannel::Stable),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReleaseChannel::Dev => "dev",
+            ReleaseChannel::Alpha => "alpha",
+            ReleaseChannel::Beta => "beta",
+            ReleaseChannel::Rc => "rc",
+            ReleaseChannel::Stable => "stable",
+        }
+    }
+}
+
+/// The version of an engine build. Versions compare by number, then channel, then build,
+/// so `4.2.1-stable` is newer than `4.2.1-rc2`, which is newer than `4.2.1-rc1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReleaseVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub channel: ReleaseChannel,
+    /// The number after the channel, e.g. `2` for `rc2`. Stable builds have none.
+    pub build: u32,
+}
+
+/// Parses the version out of an engine name such as `Godot_v4.2.1-stable_win64` or `Godot_v4.3-rc2_mono_win64`
+pub fn parse_release_version(version_name: &str) -> Option<ReleaseVersion> {
+    let version_re = Regex::new(r"v(\d+)\.(\d+)(?:\.(\d+))?(?:\.\d+)?-([a-z]+)(\d*)").unwrap();
+    let captures = version_re.captures(version_name)?;
+
+    let number = |index: usize| {
+        captures
+            .get(index)
+            .and_then(|value| value.as_str().parse::<u32>().ok())
+            .unwrap_or(0)
+    };
+
+    Some(ReleaseVersion {
+        major: number(1),
+        minor: number(2),
+        patch: number(3),
+        channel: ReleaseChannel::from_name(captures.get(4)?.as_str())?,
+        build: number(5),
+    })
+}
+
+/// Finds the executable for the engine `name` inside its installation `path`.
+/// Standard archives extract the executable directly into the installation folder, while mono archives
+/// keep it inside a nested `Godot_v…_mono_win64` folder next to the `GodotSharp` directory.
+/// Falls back to the flat layout if neither exists yet.
+fn find_executable_path(path: &Path, name: &str, suffix: &str) -> PathBuf {
+    let file_name = format!("{}{}", name, suffix);
+
+    let flat = path.join(&file_name);
+    if flat.exists() {
+        return flat;
+    }
+
+    let nested = path.join(name).join(&file_name);
+    if nested.exists() {
+        return nested;
+    }
+
+    flat
+}
+
+impl fmt::Debug for GodotEngineVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Customize so only `x` and `y` are denoted.
+        write!(
+            f,
+            "version_name: {}, version_number: {}, updated_at: {}, path: {}, download_url: {}, is_mono: {}",
+            self.version_name, self.version_number, self.updated_at, self.path, self.download_url, self.is_mono
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_release_version, ReleaseChannel};
+
+    #[test]
+    fn test_parse_release_version() {
+        let version = parse_release_version("Godot_v4.3-rc2_mono_win64").unwrap();
+        assert!(version.major == 4 && version.minor == 3 && version.patch == 0);
+        assert!(version.channel == ReleaseChannel::Rc);
+        assert!(version.build == 2);
+
+        assert!(
+            parse_release_version("Godot_v4.2.1-stable_win64")
+                > parse_release_version("Godot_v4.2.1-rc2_win64")
+        );
+        assert!(
+            parse_release_version("Godot_v4.2.10-stable_win64")
+                > parse_release_version("Godot_v4.2.9-stable_win64")
+        );
+        assert!(parse_release_version("Godot_v4.2-custom_win64").is_none());
+    }
+}
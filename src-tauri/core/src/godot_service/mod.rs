@@ -0,0 +1,6 @@
+pub mod dotnet_service;
+pub mod engine_updates;
+pub mod export_template_service;
+pub mod godot_engine_service;
+pub mod godot_engine_version;
+pub mod release_channels;
@@ -0,0 +1,226 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::godot_engine_version::{GodotEngineVersion, ReleaseChannel};
+
+/// Whether an engine build is the standard editor or the .NET (mono) editor
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReleaseFlavor {
+    Standard,
+    Mono,
+}
+
+impl ReleaseFlavor {
+    pub fn of(engine: &GodotEngineVersion) -> ReleaseFlavor {
+        if engine.is_mono {
+            ReleaseFlavor::Mono
+        } else {
+            ReleaseFlavor::Standard
+        }
+    }
+}
+
+/// The channels and flavors of the release catalog that are shown to the user
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ReleaseFilter {
+    #[serde(default = "default_channels")]
+    pub channels: Vec<ReleaseChannel>,
+    #[serde(default = "default_flavors")]
+    pub flavors: Vec<ReleaseFlavor>,
+}
+
+fn default_channels() -> Vec<ReleaseChannel> {
+    vec![ReleaseChannel::Stable]
+}
+
+fn default_flavors() -> Vec<ReleaseFlavor> {
+    vec![ReleaseFlavor::Standard, ReleaseFlavor::Mono]
+}
+
+impl Default for ReleaseFilter {
+    fn default() -> Self {
+        ReleaseFilter {
+            channels: default_channels(),
+            flavors: default_flavors(),
+        }
+    }
+}
+
+impl ReleaseFilter {
+    /// Engines whose name has no version information can't be put in a channel, so only their flavor is checked
+    pub fn matches(&self, engine: &GodotEngineVersion) -> bool {
+        let channel_visible = engine
+            .release_version()
+            .is_none_or(|version| self.channels.contains(&version.channel));
+
+        channel_visible && self.flavors.contains(&ReleaseFlavor::of(engine))
+    }
+}
+
+/// The engines of one channel and flavor, newest first
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReleaseGroup {
+    pub channel: ReleaseChannel,
+    pub flavor: ReleaseFlavor,
+    pub engines: Vec<GodotEngineVersion>,
+}
+
+/// Removes the engines that appear in both release repositories, keeping the first of each
+pub fn deduplicate_catalog(catalog: Vec<GodotEngineVersion>) -> Vec<GodotEngineVersion> {
+    let mut seen: HashSet<String> = HashSet::new();
+
+    catalog
+        .into_iter()
+        .filter(|engine| seen.insert(engine.version_name.clone()))
+        .collect()
+}
+
+/// Gets the engines in `catalog` that the `filter` shows, keeping their order
+pub fn filter_catalog(
+    catalog: &[GodotEngineVersion],
+    filter: &ReleaseFilter,
+) -> Vec<GodotEngineVersion> {
+    catalog
+        .iter()
+        .filter(|engine| filter.matches(engine))
+        .cloned()
+        .collect()
+}
+
+/// Groups the engines in `catalog` by channel and flavor, the most stable channel and standard flavor first.
+/// Engines whose name has no version information are left out.
+pub fn group_catalog(catalog: &[GodotEngineVersion]) -> Vec<ReleaseGroup> {
+    let mut groups: Vec<ReleaseGroup> = vec![];
+
+    for engine in catalog {
+        let Some(version) = engine.release_version() else {
+            continue;
+        };
+        let flavor = ReleaseFlavor::of(engine);
+
+        match groups
+            .iter_mut()
+            .find(|group| group.channel == version.channel && group.flavor == flavor)
+        {
+            Some(group) => group.engines.push(engine.clone()),
+            None => groups.push(ReleaseGroup {
+                channel: version.channel,
+                flavor,
+                engines: vec![engine.clone()],
+            }),
+        }
+    }
+
+    for group in groups.iter_mut() {
+        group
+            .engines
+            .sort_by_key(|engine| std::cmp::Reverse(engine.release_version()));
+    }
+
+    groups.sort_by_key(|group| {
+        (
+            std::cmp::Reverse(group.channel),
+            group.flavor == ReleaseFlavor::Mono,
+        )
+    });
+    groups
+}
+
+/// Finds the newest stable release of a flavor in `catalog`
+pub fn latest_stable(
+    catalog: &[GodotEngineVersion],
+    flavor: ReleaseFlavor,
+) -> Option<&GodotEngineVersion> {
+    catalog
+        .iter()
+        .filter(|engine| ReleaseFlavor::of(engine) == flavor)
+        .filter_map(|engine| Some((engine.release_version()?, engine)))
+        .filter(|(version, _)| version.channel == ReleaseChannel::Stable)
+        .max_by_key(|(version, _)| *version)
+        .map(|(_, engine)| engine)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::godot_service::godot_engine_version::{GodotEngineVersion, ReleaseChannel};
+
+    use super::{
+        deduplicate_catalog, filter_catalog, group_catalog, latest_stable, ReleaseFilter,
+        ReleaseFlavor,
+    };
+
+    fn engine(name: &str) -> GodotEngineVersion {
+        GodotEngineVersion::new(
+            name.to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        )
+    }
+
+    fn catalog() -> Vec<GodotEngineVersion> {
+        deduplicate_catalog(vec![
+            engine("Godot_v4.4-dev3_win64"),
+            engine("Godot_v4.3-rc1_win64"),
+            engine("Godot_v4.3-rc1_mono_win64"),
+            engine("Godot_v4.2.1-stable_win64"),
+            engine("Godot_v4.2.2-stable_win64"),
+            engine("Godot_v4.2.2-stable_mono_win64"),
+            engine("Godot_v4.2.2-stable_win64"),
+        ])
+    }
+
+    #[test]
+    fn test_filter_catalog() {
+        let catalog = catalog();
+        assert!(catalog.len() == 6);
+
+        let stable = filter_catalog(&catalog, &ReleaseFilter::default());
+        assert!(stable.len() == 3);
+
+        let filter = ReleaseFilter {
+            channels: vec![ReleaseChannel::Rc, ReleaseChannel::Dev],
+            flavors: vec![ReleaseFlavor::Standard],
+        };
+        let previews = filter_catalog(&catalog, &filter);
+        assert!(previews.len() == 2);
+        assert!(previews[0].version_name == "Godot_v4.4-dev3_win64");
+        assert!(previews[1].version_name == "Godot_v4.3-rc1_win64");
+    }
+
+    #[test]
+    fn test_group_catalog() {
+        let groups = group_catalog(&catalog());
+
+        assert!(groups.len() == 5);
+        assert!(groups[0].channel == ReleaseChannel::Stable);
+        assert!(groups[0].flavor == ReleaseFlavor::Standard);
+        assert!(groups[0].engines[0].version_name == "Godot_v4.2.2-stable_win64");
+        assert!(groups[0].engines[1].version_name == "Godot_v4.2.1-stable_win64");
+        assert!(groups[1].flavor == ReleaseFlavor::Mono);
+        assert!(groups[2].channel == ReleaseChannel::Rc);
+        assert!(groups[2].flavor == ReleaseFlavor::Standard);
+        assert!(groups[3].flavor == ReleaseFlavor::Mono);
+        assert!(groups[4].channel == ReleaseChannel::Dev);
+    }
+
+    #[test]
+    fn test_latest_stable() {
+        let catalog = catalog();
+
+        assert!(
+            latest_stable(&catalog, ReleaseFlavor::Standard)
+                .unwrap()
+                .version_name
+                == "Godot_v4.2.2-stable_win64"
+        );
+        assert!(
+            latest_stable(&catalog, ReleaseFlavor::Mono)
+                .unwrap()
+                .version_name
+                == "Godot_v4.2.2-stable_mono_win64"
+        );
+        assert!(latest_stable(&catalog[..2], ReleaseFlavor::Standard).is_none());
+    }
+}
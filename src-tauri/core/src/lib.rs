@@ -0,0 +1,11 @@
+pub mod command;
+pub mod directory;
+pub mod environmnet;
+pub mod fetcher;
+pub mod godot_service;
+pub mod news;
+pub mod process;
+pub mod project;
+mod test_data;
+#[cfg(test)]
+mod test_server;
@@ -0,0 +1,3 @@
+pub mod news;
+pub mod news_cache;
+pub mod news_feed;
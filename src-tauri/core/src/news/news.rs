@@ -0,0 +1,143 @@
+use regex::Regex;
+use reqwest::header::USER_AGENT;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use super::news_feed::{absolute_url, parse_feed};
+
+const NEWS_URL: &str = "https://godotengine.org/blog/";
+const NEWS_FEED_URL: &str = "https://godotengine.org/rss.xml";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NewsEntry {
+    pub title: String,
+    pub info: String,
+    pub body: String,
+    pub image_url: String,
+    pub href: String,
+    /// When the entry was first fetched, in milliseconds
+    #[serde(default)]
+    pub fetched_at: i64,
+    /// The thumbnail in the news cache, which can be loaded through the asset protocol while offline
+    #[serde(default)]
+    pub image_path: Option<String>,
+    #[serde(default)]
+    pub read: bool,
+}
+
+/// Gets the latest posts of the Godot blog from its RSS feed. The blog page is scraped instead if the feed
+/// can't be read. Returns `None` if neither can be downloaded.
+pub async fn get_news() -> Option<Vec<NewsEntry>> {
+    let client = reqwest::Client::new();
+
+    match download_news(&client, NEWS_FEED_URL)
+        .await
+        .and_then(|feed| parse_feed(&feed))
+    {
+        Ok(entries) if !entries.is_empty() => return Some(entries),
+        Ok(_) => println!("The news feed has no entries, falling back to the blog page"),
+        Err(error) => println!("{}, falling back to the blog page", error),
+    }
+
+    match download_news(&client, NEWS_URL).await {
+        Ok(html) => Some(parse_html_string(&html)),
+        Err(error) => {
+            println!("{}", error);
+            None
+        }
+    }
+}
+
+async fn download_news(client: &reqwest::Client, url: &str) -> Result<String, String> {
+    let response = client
+        .get(url)
+        .header(USER_AGENT, "My Rust Program 1.0")
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|error| format!("Could not download {}: {}", url, error))?;
+
+    response
+        .text()
+        .await
+        .map_err(|error| format!("Could not read {}: {}", url, error))
+}
+
+/// Scrapes the articles of the blog page. Articles that don't match the expected markup are skipped.
+fn parse_html_string(html: &str) -> Vec<NewsEntry> {
+    let document = Html::parse_document(html);
+
+    let selector = Selector::parse("article").unwrap();
+
+    document
+        .select(&selector)
+        .filter_map(build_news_entry)
+        .collect::<Vec<NewsEntry>>()
+}
+
+/// Gets the first text of the first element matching `selector` inside `element`
+fn select_text(element: &ElementRef, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).unwrap();
+
+    element
+        .select(&selector)
+        .next()?
+        .text()
+        .next()
+        .map(|text| text.trim().to_string())
+}
+
+fn build_news_entry(element: ElementRef) -> Option<NewsEntry> {
+    let thumbnail_selector = Selector::parse(".thumbnail").unwrap();
+    let re = Regex::new(r"\(([^)]+)\)").unwrap();
+
+    let info = [select_text(&element, ".by"), select_text(&element, ".date")]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<String>>()
+        .join(" - ");
+
+    let thumbnail = element.select(&thumbnail_selector).next()?;
+    let href = thumbnail.value().attr("href")?;
+    let image_url = re
+        .captures(&thumbnail.html())
+        .map(|captured| absolute_url(captured[1].trim_matches(['\'', '"'])))
+        .unwrap_or_default();
+
+    Some(NewsEntry {
+        title: select_text(&element, "h3")?,
+        body: select_text(&element, "p").unwrap_or_default(),
+        info,
+        image_url,
+        href: absolute_url(href),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_html_string;
+
+    #[test]
+    fn test_parsing_body() {
+        let parsed = parse_html_string(include_str!("fixtures/blog.html"));
+
+        // The article without a thumbnail is skipped instead of panicking
+        assert!(parsed.len() == 1);
+        assert!(parsed[0].title == "Maintenance release: Godot 4.2.1");
+        assert!(parsed[0].info == "By Rémi Verschelde - 12 December 2023");
+        assert!(parsed[0].body.starts_with("Godot 4.2.1 is out"));
+        assert!(
+            parsed[0].href == "https://godotengine.org/article/maintenance-release-godot-4-2-1/"
+        );
+        assert!(
+            parsed[0].image_url
+                == "https://godotengine.org/storage/blog/covers/maintenance-release-godot-4-2-1.webp"
+        );
+    }
+
+    #[test]
+    fn test_parsing_unexpected_markup() {
+        assert!(parse_html_string("<html><body><p>Redesigned</p></body></html>").is_empty());
+    }
+}
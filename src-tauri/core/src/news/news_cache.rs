@@ -0,0 +1,380 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::Local;
+use reqwest::header::USER_AGENT;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::Mutex;
+
+use crate::directory::config_directory_service::ConfigDirectoryService;
+
+use super::news::{get_news, NewsEntry};
+
+pub const DEFAULT_REFRESH_INTERVAL_MINUTES: u32 = 60;
+
+/// How often the news are fetched in the background
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct NewsSettings {
+    /// Minutes between refreshes, 0 turns off refreshing in the background
+    #[serde(
+        rename(
+            serialize = "refreshIntervalMinutes",
+            deserialize = "refreshIntervalMinutes"
+        ),
+        default = "default_refresh_interval"
+    )]
+    pub refresh_interval_minutes: u32,
+}
+
+fn default_refresh_interval() -> u32 {
+    DEFAULT_REFRESH_INTERVAL_MINUTES
+}
+
+impl Default for NewsSettings {
+    fn default() -> Self {
+        NewsSettings {
+            refresh_interval_minutes: DEFAULT_REFRESH_INTERVAL_MINUTES,
+        }
+    }
+}
+
+/// The news entries of the last successful fetch and which of them were read, kept on disk for offline use
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct NewsCache {
+    /// When the news were last fetched, in milliseconds
+    #[serde(default)]
+    pub fetched_at: Option<i64>,
+    #[serde(default)]
+    pub entries: Vec<NewsEntry>,
+    /// The `href` of every entry that was read
+    #[serde(default)]
+    pub read: HashSet<String>,
+}
+
+impl NewsCache {
+    /// The cache is stale once `refresh_interval_minutes` passed since the last fetch, or if nothing was fetched yet.
+    /// With an interval of 0 the news are only fetched once and then on request.
+    pub fn is_stale(&self, refresh_interval_minutes: u32, now: i64) -> bool {
+        self.fetched_at.is_none_or(|fetched_at| {
+            refresh_interval_minutes > 0
+                && now - fetched_at >= i64::from(refresh_interval_minutes) * 60 * 1000
+        })
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| !self.read.contains(&entry.href))
+            .count()
+    }
+
+    /// Replaces the cached entries with freshly fetched ones. Entries that were already cached keep the time
+    /// they were first fetched and their cached thumbnail, and read state is dropped for entries no longer in the feed.
+    pub fn merge(&mut self, fetched: Vec<NewsEntry>, now: i64) {
+        let entries: Vec<NewsEntry> = fetched
+            .into_iter()
+            .map(|mut entry| {
+                if let Some(cached) = self.entries.iter().find(|cached| cached.href == entry.href) {
+                    entry.fetched_at = cached.fetched_at;
+                    if entry.image_path.is_none() && cached.image_url == entry.image_url {
+                        entry.image_path = cached.image_path.clone();
+                    }
+                } else {
+                    entry.fetched_at = now;
+                }
+                entry
+            })
+            .collect();
+
+        self.read
+            .retain(|href| entries.iter().any(|entry| &entry.href == href));
+        self.entries = entries;
+        self.fetched_at = Some(now);
+    }
+
+    pub fn set_read(&mut self, hrefs: &[String], read: bool) {
+        for href in hrefs {
+            if read {
+                self.read.insert(href.clone());
+            } else {
+                self.read.remove(href);
+            }
+        }
+    }
+}
+
+/// The cached news as shown to the user
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NewsResponse {
+    pub entries: Vec<NewsEntry>,
+    #[serde(rename(serialize = "unreadCount", deserialize = "unreadCount"))]
+    pub unread_count: usize,
+    #[serde(rename(serialize = "fetchedAt", deserialize = "fetchedAt"))]
+    pub fetched_at: Option<i64>,
+}
+
+impl From<&NewsCache> for NewsResponse {
+    fn from(cache: &NewsCache) -> Self {
+        NewsResponse {
+            entries: cache
+                .entries
+                .iter()
+                .map(|entry| NewsEntry {
+                    read: cache.read.contains(&entry.href),
+                    ..entry.clone()
+                })
+                .collect(),
+            unread_count: cache.unread_count(),
+            fetched_at: cache.fetched_at,
+        }
+    }
+}
+
+fn news_cache_file(news_path: &Path) -> PathBuf {
+    news_path.join("news.json")
+}
+
+pub fn load_news_cache(news_path: &Path) -> NewsCache {
+    fs::read_to_string(news_cache_file(news_path))
+        .ok()
+        .and_then(|cached| serde_json::from_str(&cached).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_news_cache(news_path: &Path, cache: &NewsCache) -> std::io::Result<()> {
+    fs::create_dir_all(news_path)?;
+    fs::write(
+        news_cache_file(news_path),
+        serde_json::to_string(cache).unwrap(),
+    )
+}
+
+/// Loads the news cache, lets `update` change it and saves it again. `lock` is held meanwhile, so changes made
+/// at the same time, e.g. marking entries as read during a refresh, aren't lost.
+pub async fn update_news_cache(
+    news_path: &Path,
+    lock: &Mutex<()>,
+    update: impl FnOnce(&mut NewsCache),
+) -> std::io::Result<NewsCache> {
+    let _guard = lock.lock().await;
+
+    let mut cache = load_news_cache(news_path);
+    update(&mut cache);
+    save_news_cache(news_path, &cache)?;
+
+    Ok(cache)
+}
+
+/// Gets the file a thumbnail is cached in, named after a hash of its url
+pub fn image_cache_file(news_path: &Path, image_url: &str) -> PathBuf {
+    let hash = Sha256::digest(image_url.as_bytes());
+    let extension = Path::new(image_url.split(['?', '#']).next().unwrap_or(""))
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .filter(|extension| {
+            extension.len() <= 4 && extension.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        .unwrap_or("png".to_string());
+
+    news_path
+        .join("images")
+        .join(format!("{:x}.{}", hash, extension))
+}
+
+/// Downloads the thumbnails of `entries` that aren't cached yet. Entries whose thumbnail can't be downloaded
+/// keep showing the remote image.
+async fn cache_news_images(client: &reqwest::Client, news_path: &Path, entries: &mut [NewsEntry]) {
+    for entry in entries.iter_mut() {
+        if entry.image_url.is_empty() {
+            continue;
+        }
+
+        let target = image_cache_file(news_path, &entry.image_url);
+        if !target.exists() {
+            let downloaded = download_image(client, &entry.image_url, &target).await;
+            if let Err(error) = downloaded {
+                println!("Could not cache {}: {}", entry.image_url, error);
+                continue;
+            }
+        }
+
+        entry.image_path = Some(target.to_string_lossy().to_string());
+    }
+}
+
+async fn download_image(
+    client: &reqwest::Client,
+    url: &str,
+    target: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = client
+        .get(url)
+        .header(USER_AGENT, "My Rust Program 1.0")
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    fs::create_dir_all(target.parent().unwrap())?;
+    fs::write(target, bytes)?;
+    Ok(())
+}
+
+/// Removes the cached thumbnails that no entry uses anymore
+fn prune_news_images(news_path: &Path, entries: &[NewsEntry]) {
+    let Ok(images) = fs::read_dir(news_path.join("images")) else {
+        return;
+    };
+
+    let used: HashSet<PathBuf> = entries
+        .iter()
+        .filter_map(|entry| entry.image_path.as_ref().map(PathBuf::from))
+        .collect();
+
+    for image in images.filter_map(|image| image.ok()) {
+        if !used.contains(&image.path()) {
+            fs::remove_file(image.path()).ok();
+        }
+    }
+}
+
+/// Fetches the news, caches their thumbnails and stores them in the news cache.
+/// The cache is left untouched if the news can't be fetched. Only storing them holds `lock`, so the cache
+/// can be used while the news are downloaded.
+pub async fn refresh_news(
+    config_directory: &ConfigDirectoryService,
+    lock: &Mutex<()>,
+) -> Result<NewsCache, String> {
+    let news_path = config_directory.news_path();
+    let mut fetched = get_news()
+        .await
+        .ok_or("Could not load the news feed or the blog page".to_string())?;
+
+    cache_news_images(&reqwest::Client::new(), &news_path, &mut fetched).await;
+
+    update_news_cache(&news_path, lock, |cache| {
+        cache.merge(fetched, Local::now().timestamp_millis());
+        prune_news_images(&news_path, &cache.entries);
+    })
+    .await
+    .map_err(|error| format!("Could not save the news cache: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::news::news::NewsEntry;
+
+    use tokio::sync::Mutex;
+
+    use super::{
+        image_cache_file, load_news_cache, save_news_cache, update_news_cache, NewsResponse,
+    };
+
+    fn entry(href: &str, image_url: &str) -> NewsEntry {
+        NewsEntry {
+            title: href.to_string(),
+            info: "".to_string(),
+            body: "".to_string(),
+            image_url: image_url.to_string(),
+            href: href.to_string(),
+            fetched_at: 0,
+            image_path: None,
+            read: false,
+        }
+    }
+
+    #[test]
+    fn test_news_cache() {
+        let path = PathBuf::from("./test-data-news-cache");
+        let mut cache = load_news_cache(&path);
+
+        assert!(cache.is_stale(60, 0));
+        assert!(cache.is_stale(0, 0));
+
+        cache.merge(
+            vec![entry("/first", "/first.webp"), entry("/second", "")],
+            1000,
+        );
+        cache.entries[0].image_path = Some("first.webp".to_string());
+        cache.set_read(&["/first".to_string()], true);
+
+        assert!(!cache.is_stale(60, 1000 + 59 * 60 * 1000));
+        assert!(cache.is_stale(60, 1000 + 60 * 60 * 1000));
+        assert!(!cache.is_stale(0, 1000 + 24 * 60 * 60 * 1000));
+        assert!(cache.unread_count() == 1);
+
+        save_news_cache(&path, &cache).unwrap();
+        let mut cache = load_news_cache(&path);
+
+        // Entries that are still in the feed keep their state, the others are dropped
+        cache.merge(
+            vec![entry("/third", ""), entry("/first", "/first.webp")],
+            5000,
+        );
+
+        assert!(cache.fetched_at == Some(5000));
+        assert!(cache.entries[0].fetched_at == 5000);
+        assert!(cache.entries[1].fetched_at == 1000);
+        assert!(cache.entries[1].image_path == Some("first.webp".to_string()));
+        assert!(cache.unread_count() == 1);
+
+        let response = NewsResponse::from(&cache);
+        assert!(!response.entries[0].read);
+        assert!(response.entries[1].read);
+        assert!(response.unread_count == 1);
+
+        cache.set_read(&["/first".to_string()], false);
+        assert!(cache.unread_count() == 2);
+
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_news_cache() {
+        let path = PathBuf::from("./test-data-news-update");
+        let lock = Mutex::new(());
+
+        let (merged, marked) = tokio::join!(
+            update_news_cache(&path, &lock, |cache| {
+                cache.merge(vec![entry("/first", "")], 1000)
+            }),
+            update_news_cache(&path, &lock, |cache| {
+                cache.set_read(&["/first".to_string()], true)
+            }),
+        );
+        merged.unwrap();
+        marked.unwrap();
+
+        // Neither change overwrote the other
+        let cache = load_news_cache(&path);
+        assert!(cache.fetched_at == Some(1000));
+        assert!(cache.read.contains("/first"));
+
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn test_image_cache_file() {
+        let path = PathBuf::from("news");
+
+        let image = image_cache_file(&path, "https://godotengine.org/covers/release.webp?v=2");
+        assert!(image.starts_with(path.join("images")));
+        assert!(image.extension().unwrap() == "webp");
+
+        let other = image_cache_file(&path, "https://godotengine.org/covers/other.webp");
+        assert!(image != other);
+        assert!(
+            image_cache_file(&path, "https://godotengine.org/cover")
+                .extension()
+                .unwrap()
+                == "png"
+        );
+    }
+}
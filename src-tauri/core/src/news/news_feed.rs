@@ -0,0 +1,169 @@
+use feed_rs::model::Entry;
+use scraper::{Html, Selector};
+
+use super::news::NewsEntry;
+
+const GODOT_URL: &str = "https://godotengine.org";
+
+/// Parses an RSS or Atom feed of the Godot blog into news entries
+pub fn parse_feed(xml: &str) -> Result<Vec<NewsEntry>, String> {
+    let feed = feed_rs::parser::parse(xml.as_bytes())
+        .map_err(|error| format!("Could not parse news feed: {}", error))?;
+
+    Ok(feed.entries.iter().map(build_feed_entry).collect())
+}
+
+fn build_feed_entry(entry: &Entry) -> NewsEntry {
+    let html = entry
+        .summary
+        .as_ref()
+        .map(|summary| summary.content.clone())
+        .or(entry
+            .content
+            .as_ref()
+            .and_then(|content| content.body.clone()))
+        .unwrap_or_default();
+
+    let authors: Vec<String> = entry
+        .authors
+        .iter()
+        .map(|author| author.name.clone())
+        .collect();
+    let date = entry
+        .published
+        .or(entry.updated)
+        .map(|date| date.format("%-d %B %Y").to_string());
+
+    let info = match (authors.is_empty(), date) {
+        (false, Some(date)) => format!("By {} - {}", authors.join(", "), date),
+        (false, None) => format!("By {}", authors.join(", ")),
+        (true, Some(date)) => date,
+        (true, None) => "".to_string(),
+    };
+
+    NewsEntry {
+        title: entry
+            .title
+            .as_ref()
+            .map(|title| title.content.trim().to_string())
+            .unwrap_or_default(),
+        info,
+        body: html_to_text(&html),
+        image_url: entry_image_url(entry, &html).unwrap_or_default(),
+        href: entry
+            .links
+            .iter()
+            .find(|link| link.rel.as_deref().is_none_or(|rel| rel == "alternate"))
+            .map(|link| absolute_url(&link.href))
+            .unwrap_or_default(),
+        ..Default::default()
+    }
+}
+
+/// Finds the cover image of an entry in its media thumbnails, its enclosures, or else the first image in its summary
+fn entry_image_url(entry: &Entry, html: &str) -> Option<String> {
+    let thumbnail = entry
+        .media
+        .iter()
+        .flat_map(|media| media.thumbnails.iter())
+        .map(|thumbnail| thumbnail.image.uri.clone())
+        .next();
+
+    let media_content = entry
+        .media
+        .iter()
+        .flat_map(|media| media.content.iter())
+        .filter_map(|content| content.url.as_ref().map(|url| url.to_string()))
+        .next();
+
+    let enclosure = entry
+        .links
+        .iter()
+        .find(|link| {
+            link.rel.as_deref() == Some("enclosure")
+                && link
+                    .media_type
+                    .as_deref()
+                    .is_none_or(|media_type| media_type.starts_with("image/"))
+        })
+        .map(|link| link.href.clone());
+
+    let image_selector = Selector::parse("img").unwrap();
+    let inline_image = Html::parse_fragment(html)
+        .select(&image_selector)
+        .find_map(|image| image.value().attr("src").map(|src| src.to_string()));
+
+    thumbnail
+        .or(media_content)
+        .or(enclosure)
+        .or(inline_image)
+        .map(|url| absolute_url(&url))
+}
+
+fn html_to_text(html: &str) -> String {
+    Html::parse_fragment(html)
+        .root_element()
+        .text()
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// The blog links to its own pages and images with paths relative to the site
+pub fn absolute_url(url: &str) -> String {
+    if url.starts_with('/') {
+        format!("{}{}", GODOT_URL, url)
+    } else {
+        url.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_feed;
+
+    #[test]
+    fn test_parse_rss_feed() {
+        let entries = parse_feed(include_str!("fixtures/blog_rss.xml")).unwrap();
+
+        assert!(entries.len() == 2);
+        assert!(entries[0].title == "Maintenance release: Godot 4.2.1");
+        assert!(entries[0].info == "By Rémi Verschelde - 12 December 2023");
+        assert!(
+            entries[0].body
+                == "Godot 4.2.1 is out, fixing a number of bugs found since the 4.2 release."
+        );
+        assert!(
+            entries[0].href == "https://godotengine.org/article/maintenance-release-godot-4-2-1/"
+        );
+        assert!(
+            entries[0].image_url
+                == "https://godotengine.org/storage/blog/covers/maintenance-release-godot-4-2-1.webp"
+        );
+        assert!(
+            entries[1].image_url
+                == "https://godotengine.org/storage/blog/covers/godot-4-2-arrives-in-style.webp"
+        );
+    }
+
+    #[test]
+    fn test_parse_atom_feed() {
+        let entries = parse_feed(include_str!("fixtures/blog_atom.xml")).unwrap();
+
+        assert!(entries.len() == 1);
+        assert!(entries[0].title == "Maintenance release: Godot 4.2.1");
+        assert!(entries[0].info == "By Rémi Verschelde - 12 December 2023");
+        assert!(entries[0].body.starts_with("Godot 4.2.1 is out"));
+        assert!(
+            entries[0].href == "https://godotengine.org/article/maintenance-release-godot-4-2-1/"
+        );
+        assert!(entries[0]
+            .image_url
+            .ends_with("maintenance-release-godot-4-2-1.webp"));
+    }
+
+    #[test]
+    fn test_parse_invalid_feed() {
+        assert!(parse_feed("<html><body>Not a feed</body></html>").is_err());
+    }
+}
@@ -0,0 +1,360 @@
+use std::{
+    cell::OnceCell,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::Arc,
+};
+
+use super::process_registry::{ProcessInfo, ProcessListener, ProcessOutput};
+
+/// Gets the lock file the manager writes into a project's `.godot/editor` folder while one of its editors has the
+/// project open. It holds the process id and start time of the editor, so editors started by the CLI are detected
+/// after it exited.
+pub fn editor_lock_path(project_directory: &Path) -> PathBuf {
+    project_directory
+        .join(".godot")
+        .join("editor")
+        .join("godot_manager.lock")
+}
+
+/// Writes the editor lock of a project, with the start time of the editor so the lock doesn't match another
+/// process that reuses its id later
+pub fn write_editor_lock(project_directory: &Path, id: u32) -> std::io::Result<()> {
+    let start_time = process_start_time(id).unwrap_or_default();
+    let path = editor_lock_path(project_directory);
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(path, format!("{}\n{}", id, start_time))
+}
+
+pub fn remove_editor_lock(project_directory: &Path) {
+    fs::remove_file(editor_lock_path(project_directory)).ok();
+}
+
+/// The command lines of the running processes, read on first use and then shared by the editor checks of
+/// several projects, since reading them starts a process on Windows and macOS
+#[derive(Default)]
+pub struct ProcessSnapshot {
+    command_lines: OnceCell<Vec<(u32, String)>>,
+}
+
+impl ProcessSnapshot {
+    pub fn new() -> ProcessSnapshot {
+        ProcessSnapshot::default()
+    }
+
+    fn command_lines(&self) -> &[(u32, String)] {
+        self.command_lines.get_or_init(running_command_lines)
+    }
+}
+
+/// Gets the process id of the editor that has the project open. Editors started by the manager are found
+/// through the editor lock, which is removed if that editor is no longer running. Other editors are found
+/// by a running Godot process that was started with the project folder or its `project.godot` file.
+pub fn locked_editor_id(project_directory: &Path) -> Option<u32> {
+    find_locked_editor(project_directory, &ProcessSnapshot::new())
+}
+
+/// Like [`locked_editor_id`], but looks for other editors in `processes`, to check many projects at once
+pub fn find_locked_editor(project_directory: &Path, processes: &ProcessSnapshot) -> Option<u32> {
+    locked_id(project_directory).or_else(|| find_running_editor(project_directory, processes))
+}
+
+fn locked_id(project_directory: &Path) -> Option<u32> {
+    let lock = fs::read_to_string(editor_lock_path(project_directory)).ok()?;
+    let mut lines = lock.lines();
+    let id: u32 = lines.next()?.trim().parse().ok()?;
+    let start_time = lines.next().unwrap_or_default().trim();
+
+    // Locks without a start time can't tell the editor apart from a process that reused its id
+    if !start_time.is_empty() && process_start_time(id).as_deref() == Some(start_time) {
+        Some(id)
+    } else {
+        remove_editor_lock(project_directory);
+        None
+    }
+}
+
+/// Gets the start time of the process with `id` in a platform specific format, or none if it isn't running.
+/// Together with the id it identifies a process, since ids are reused once a process exits.
+pub fn process_start_time(id: u32) -> Option<String> {
+    if cfg!(target_os = "linux") {
+        // The start time is the 22nd field, counted after the executable name which can contain spaces
+        let stat = fs::read_to_string(format!("/proc/{}/stat", id)).ok()?;
+        let fields = &stat[stat.rfind(')')? + 1..];
+        fields
+            .split_whitespace()
+            .nth(19)
+            .map(|start_time| start_time.to_string())
+    } else if cfg!(target_os = "windows") {
+        windows_process_start_time(id)
+    } else {
+        command_output("ps", &["-o", "lstart=", "-p", &id.to_string()])
+    }
+}
+
+/// Reads the creation time of a running process through the Windows API, as the number of 100 nanosecond
+/// intervals since 1601 that PowerShell's `StartTime.ToFileTimeUtc()` gives
+#[cfg(target_os = "windows")]
+fn windows_process_start_time(id: u32) -> Option<String> {
+    use windows_sys::Win32::{
+        Foundation::{CloseHandle, FILETIME, STILL_ACTIVE},
+        System::Threading::{
+            GetExitCodeProcess, GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+        },
+    };
+
+    let empty = || FILETIME {
+        dwLowDateTime: 0,
+        dwHighDateTime: 0,
+    };
+    let (mut creation, mut exit, mut kernel, mut user) = (empty(), empty(), empty(), empty());
+    let mut exit_code: u32 = 0;
+
+    // SAFETY: the handle is checked before use and closed once, and the out pointers point to locals
+    let (is_running, has_times) = unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, id);
+        if handle.is_null() {
+            return None;
+        }
+
+        // An exited process can still be opened while other handles to it are open
+        let is_running =
+            GetExitCodeProcess(handle, &mut exit_code) != 0 && exit_code == STILL_ACTIVE as u32;
+        let has_times =
+            GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user) != 0;
+        CloseHandle(handle);
+
+        (is_running, has_times)
+    };
+
+    (is_running && has_times).then(|| {
+        (((creation.dwHighDateTime as u64) << 32) | creation.dwLowDateTime as u64).to_string()
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn windows_process_start_time(_id: u32) -> Option<String> {
+    None
+}
+
+/// Finds a running Godot editor that has the project open, going by the command lines of all processes
+fn find_running_editor(project_directory: &Path, processes: &ProcessSnapshot) -> Option<u32> {
+    processes
+        .command_lines()
+        .iter()
+        .find(|(id, command_line)| {
+            *id != std::process::id() && is_editor_command_line(command_line, project_directory)
+        })
+        .map(|(id, _)| *id)
+}
+
+/// Checks if `command_line` starts a Godot editor on the project, either with `--editor`/`-e` and the project
+/// folder or with the project's `project.godot` file
+pub fn is_editor_command_line(command_line: &str, project_directory: &Path) -> bool {
+    let normalize = |text: &str| {
+        let text = text.replace('\\', "/");
+        if cfg!(target_os = "windows") {
+            text.to_lowercase()
+        } else {
+            text
+        }
+    };
+
+    let command_line = normalize(command_line);
+    let directory = normalize(&project_directory.to_string_lossy());
+    let directory = directory.trim_end_matches('/');
+
+    // The executable can be quoted if its path contains spaces
+    let executable = match command_line.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next(),
+        None => command_line.split_whitespace().next(),
+    };
+    if directory.is_empty() || !executable.is_some_and(|path| path.to_lowercase().contains("godot"))
+    {
+        return false;
+    }
+
+    let is_editor = command_line
+        .split_whitespace()
+        .any(|argument| argument == "-e" || argument == "--editor");
+
+    command_line.match_indices(directory).any(|(index, _)| {
+        let after = &command_line[index + directory.len()..];
+        // The project folder must end here, so `my_game` doesn't match `my_game_2`
+        if !(after.is_empty() || after.starts_with([' ', '"', '\'', '/'])) {
+            return false;
+        }
+
+        let rest = after.trim_start_matches('/');
+        rest.starts_with("project.godot")
+            || (is_editor && (rest.is_empty() || rest.starts_with([' ', '"', '\''])))
+    })
+}
+
+/// Gets the id and command line of every running process
+fn running_command_lines() -> Vec<(u32, String)> {
+    if cfg!(target_os = "linux") {
+        let Ok(processes) = fs::read_dir("/proc") else {
+            return vec![];
+        };
+
+        processes
+            .filter_map(|process| process.ok())
+            .filter_map(|process| {
+                let id: u32 = process.file_name().to_str()?.parse().ok()?;
+                let command_line = fs::read(process.path().join("cmdline")).ok()?;
+                let command_line = String::from_utf8_lossy(&command_line).replace('\0', " ");
+                Some((id, command_line.trim().to_string()))
+            })
+            .collect()
+    } else {
+        let output = if cfg!(target_os = "windows") {
+            command_output(
+                "powershell",
+                &[
+                    "-NoProfile",
+                    "-Command",
+                    "Get-CimInstance Win32_Process | ForEach-Object { \"$($_.ProcessId) $($_.CommandLine)\" }",
+                ],
+            )
+        } else {
+            command_output("ps", &["-axo", "pid=,command="])
+        };
+
+        output
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let (id, command_line) = line.trim().split_once(' ')?;
+                Some((id.parse().ok()?, command_line.trim().to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Runs `program` and gets its trimmed output, or none if it failed or printed nothing
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let output = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    (!output.is_empty()).then_some(output)
+}
+
+/// Fails with [`already_open_message`] if an editor has the project open. `running_editor` is the editor the
+/// caller itself started for the project, if any.
+pub fn ensure_not_open(
+    project_name: &str,
+    project_directory: &Path,
+    running_editor: Option<u32>,
+) -> Result<(), String> {
+    match running_editor.or_else(|| locked_editor_id(project_directory)) {
+        Some(id) => Err(already_open_message(project_name, id)),
+        None => Ok(()),
+    }
+}
+
+/// The message shown when a project is opened while an editor with process id `id` has it open
+pub fn already_open_message(project_name: &str, id: u32) -> String {
+    format!(
+        "{} is already open in an editor (process {}). Opening it twice can corrupt the .godot import cache, close the other editor first or force open the project.",
+        project_name, id
+    )
+}
+
+/// Removes the editor lock of a project once its editor exits, then passes the event on to `inner`
+pub struct EditorLockListener {
+    pub project_directory: PathBuf,
+    pub inner: Arc<dyn ProcessListener>,
+}
+
+impl ProcessListener for EditorLockListener {
+    fn output(&self, output: &ProcessOutput) {
+        self.inner.output(output);
+    }
+
+    fn exited(&self, process: &ProcessInfo) {
+        remove_editor_lock(&self.project_directory);
+        self.inner.exited(process);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    use super::{
+        editor_lock_path, find_locked_editor, is_editor_command_line, locked_editor_id,
+        process_start_time, write_editor_lock, ProcessSnapshot,
+    };
+
+    #[test]
+    fn test_editor_lock() {
+        let path = PathBuf::from("./test-data-editor-lock/my_game");
+        fs::create_dir_all(&path).unwrap();
+
+        assert!(locked_editor_id(&path).is_none());
+
+        let id = std::process::id();
+        assert!(process_start_time(id).is_some());
+        write_editor_lock(&path, id).unwrap();
+        assert!(locked_editor_id(&path) == Some(id));
+
+        let processes = ProcessSnapshot::new();
+        assert!(find_locked_editor(&path, &processes) == Some(id));
+        assert!(
+            find_locked_editor(Path::new("./test-data-editor-lock/other"), &processes).is_none()
+        );
+
+        // Another process reused the id of the editor
+        fs::write(editor_lock_path(&path), format!("{}\n1", id)).unwrap();
+        assert!(locked_editor_id(&path).is_none());
+        assert!(!editor_lock_path(&path).exists());
+
+        write_editor_lock(&path, 99_999_999).unwrap();
+        assert!(process_start_time(99_999_999).is_none());
+        assert!(locked_editor_id(&path).is_none());
+        assert!(!editor_lock_path(&path).exists());
+
+        fs::remove_dir_all("./test-data-editor-lock").unwrap();
+    }
+
+    #[test]
+    fn test_editor_command_line() {
+        let project = Path::new("/home/me/games/my_game");
+
+        assert!(is_editor_command_line(
+            "/opt/godot/Godot_v4.2.1-stable_linux.x86_64 --editor --path /home/me/games/my_game",
+            project
+        ));
+        assert!(is_editor_command_line(
+            "godot -e --path /home/me/games/my_game/",
+            project
+        ));
+        assert!(is_editor_command_line(
+            "godot /home/me/games/my_game/project.godot",
+            project
+        ));
+
+        // A running game, another project, and another program
+        assert!(!is_editor_command_line(
+            "godot --path /home/me/games/my_game",
+            project
+        ));
+        assert!(!is_editor_command_line(
+            "godot -e --path /home/me/games/my_game_2",
+            project
+        ));
+        assert!(!is_editor_command_line(
+            "code -e /home/me/games/my_game",
+            project
+        ));
+    }
+}
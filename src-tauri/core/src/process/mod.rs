@@ -0,0 +1,3 @@
+pub mod editor_lock;
+pub mod process_log;
+pub mod process_registry;
@@ -0,0 +1,122 @@
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use chrono::Local;
+
+/// How many log files are kept per project before the oldest ones are deleted
+pub const MAX_LOG_FILES: usize = 10;
+
+/// Replaces characters that are not allowed in folder names, so any project or engine name can be used as a log folder
+pub fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|character| match character {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            _ => character,
+        })
+        .collect()
+}
+
+/// Creates a new log file for a launch of `name` in `logs_path/<name>/`, deleting the oldest logs
+/// so no more than `MAX_LOG_FILES` remain
+pub fn create_log_file(
+    logs_path: &Path,
+    name: &str,
+    kind: &str,
+) -> std::io::Result<(PathBuf, File)> {
+    let directory = logs_path.join(sanitize_name(name));
+    fs::create_dir_all(&directory)?;
+
+    let file_name = format!(
+        "{}_{}.log",
+        Local::now().format("%Y-%m-%d_%H-%M-%S%.3f"),
+        kind.to_lowercase()
+    );
+    let path = directory.join(file_name);
+    let file = File::create(&path)?;
+
+    rotate_logs(&directory, MAX_LOG_FILES)?;
+
+    Ok((path, file))
+}
+
+/// Deletes the oldest `.log` files in `directory` until at most `keep` remain.
+/// Log file names start with their creation time, so sorting by name sorts them by age.
+pub fn rotate_logs(directory: &Path, keep: usize) -> std::io::Result<()> {
+    let mut logs: Vec<PathBuf> = fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "log"))
+        .collect();
+
+    if logs.len() <= keep {
+        return Ok(());
+    }
+
+    logs.sort();
+    for path in &logs[..logs.len() - keep] {
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
+/// Gets the last `max_lines` lines of a log file
+pub fn tail_log(path: &Path, max_lines: usize) -> std::io::Result<Vec<String>> {
+    let reader = BufReader::new(File::open(path)?);
+    let lines: Vec<String> = reader.lines().map_while(Result::ok).collect();
+
+    let start = lines.len().saturating_sub(max_lines);
+    Ok(lines[start..].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{create_log_file, rotate_logs, tail_log};
+
+    #[test]
+    fn test_create_and_rotate_logs() {
+        let path = PathBuf::from("./test-data-logs");
+
+        let (log_path, _) = create_log_file(&path, "my:game", "Editor").unwrap();
+        assert!(log_path.starts_with(path.join("my_game")));
+        assert!(log_path.to_string_lossy().ends_with("_editor.log"));
+
+        for index in 0..5 {
+            fs::write(
+                path.join("my_game")
+                    .join(format!("2020-01-0{}_00-00-00.000_game.log", index)),
+                "",
+            )
+            .unwrap();
+        }
+
+        rotate_logs(&path.join("my_game"), 3).unwrap();
+
+        let remaining = fs::read_dir(path.join("my_game")).unwrap().count();
+        assert!(remaining == 3);
+        assert!(log_path.exists());
+        assert!(!path
+            .join("my_game")
+            .join("2020-01-00_00-00-00.000_game.log")
+            .exists());
+
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn test_tail_log() {
+        let path = PathBuf::from("./test-data-tail");
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("game.log"), "one\ntwo\nthree\n").unwrap();
+
+        assert!(tail_log(&path.join("game.log"), 2).unwrap() == vec!["two", "three"]);
+        assert!(tail_log(&path.join("game.log"), 10).unwrap().len() == 3);
+
+        fs::remove_dir_all(path).unwrap();
+    }
+}
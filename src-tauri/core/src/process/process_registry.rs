@@ -0,0 +1,458 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read, Write},
+    path::Path,
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use super::process_log;
+
+/// How long the output of an exited process is still read before its exit is recorded
+const READER_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ProcessKind {
+    Editor,
+    Game,
+    /// A headless export, import or script run
+    Task,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// An editor or game launched by the manager as shown to the frontend
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProcessInfo {
+    pub id: u32,
+    /// The project that was launched, or none if the engine was opened on its own
+    #[serde(rename(serialize = "projectName", deserialize = "projectName"))]
+    pub project_name: Option<String>,
+    #[serde(rename(serialize = "engineName", deserialize = "engineName"))]
+    pub engine_name: String,
+    pub kind: ProcessKind,
+    #[serde(rename(serialize = "startedAt", deserialize = "startedAt"))]
+    pub started_at: i64,
+    #[serde(rename(serialize = "logPath", deserialize = "logPath"))]
+    pub log_path: String,
+    #[serde(rename(serialize = "exitCode", deserialize = "exitCode"))]
+    pub exit_code: Option<i32>,
+    #[serde(rename(serialize = "exitedAt", deserialize = "exitedAt"))]
+    pub exited_at: Option<i64>,
+    /// Whether the process exited unsuccessfully without being stopped through the manager
+    pub crashed: bool,
+}
+
+impl ProcessInfo {
+    pub fn is_running(&self) -> bool {
+        self.exited_at.is_none()
+    }
+}
+
+/// A line written by a launched process
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProcessOutput {
+    pub id: u32,
+    pub stream: OutputStream,
+    pub line: String,
+}
+
+/// Receives the output and exit of launched processes, e.g. to forward them to the frontend as events
+pub trait ProcessListener: Send + Sync {
+    fn output(&self, output: &ProcessOutput);
+
+    fn exited(&self, process: &ProcessInfo);
+}
+
+/// Discards all process events
+pub struct NoListener;
+
+impl ProcessListener for NoListener {
+    fn output(&self, _output: &ProcessOutput) {}
+
+    fn exited(&self, _process: &ProcessInfo) {}
+}
+
+struct TrackedProcess {
+    info: Arc<Mutex<ProcessInfo>>,
+    child: Arc<Mutex<Child>>,
+    stopped: Arc<Mutex<bool>>,
+}
+
+/// Keeps every editor and game started by the manager so they can be listed and stopped.
+/// The output of each process is written to a log file and its exit code is recorded once it exits.
+#[derive(Default)]
+pub struct ProcessRegistry {
+    processes: Vec<TrackedProcess>,
+}
+
+impl ProcessRegistry {
+    pub fn new() -> ProcessRegistry {
+        ProcessRegistry::default()
+    }
+
+    /// Spawns `command` and supervises it. Its output is logged to a new file in `logs_path`
+    /// under the project name, or the engine name if no project is given.
+    pub fn spawn(
+        &mut self,
+        mut command: Command,
+        project_name: Option<&str>,
+        engine_name: &str,
+        kind: ProcessKind,
+        logs_path: &Path,
+        listener: Arc<dyn ProcessListener>,
+    ) -> std::io::Result<ProcessInfo> {
+        let (log_path, log_file) = process_log::create_log_file(
+            logs_path,
+            project_name.unwrap_or(engine_name),
+            &format!("{:?}", kind),
+        )?;
+
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let info = ProcessInfo {
+            id: child.id(),
+            project_name: project_name.map(|name| name.to_string()),
+            engine_name: engine_name.to_string(),
+            kind,
+            started_at: Local::now().timestamp_millis(),
+            log_path: log_path.to_string_lossy().to_string(),
+            exit_code: None,
+            exited_at: None,
+            crashed: false,
+        };
+
+        let log_file = Arc::new(Mutex::new(log_file));
+        let readers = vec![
+            child.stdout.take().map(|stdout| {
+                read_output(
+                    stdout,
+                    info.id,
+                    OutputStream::Stdout,
+                    log_file.clone(),
+                    listener.clone(),
+                )
+            }),
+            child.stderr.take().map(|stderr| {
+                read_output(
+                    stderr,
+                    info.id,
+                    OutputStream::Stderr,
+                    log_file.clone(),
+                    listener.clone(),
+                )
+            }),
+        ];
+
+        let tracked = TrackedProcess {
+            info: Arc::new(Mutex::new(info.clone())),
+            child: Arc::new(Mutex::new(child)),
+            stopped: Arc::new(Mutex::new(false)),
+        };
+
+        let process_info = tracked.info.clone();
+        let process_child = tracked.child.clone();
+        let stopped = tracked.stopped.clone();
+
+        thread::spawn(move || {
+            // The child is polled on its own, since a process it started can keep the pipes open after it exited
+            let status = loop {
+                match process_child.lock().unwrap().try_wait() {
+                    Ok(Some(status)) => break Some(status),
+                    Ok(None) => {}
+                    Err(_) => break None,
+                }
+                thread::sleep(Duration::from_millis(100));
+            };
+
+            join_readers(readers.into_iter().flatten().collect());
+
+            let mut info = process_info.lock().unwrap();
+            info.exit_code = status.and_then(|status| status.code());
+            info.exited_at = Some(Local::now().timestamp_millis());
+            info.crashed =
+                !*stopped.lock().unwrap() && !status.is_some_and(|status| status.success());
+
+            writeln!(
+                log_file.lock().unwrap(),
+                "-- Process exited with code {:?} --",
+                info.exit_code
+            )
+            .ok();
+
+            listener.exited(&info);
+        });
+
+        self.processes.push(tracked);
+
+        Ok(info)
+    }
+
+    /// Gets all processes started since the manager was opened, including the ones that exited
+    pub fn processes(&self) -> Vec<ProcessInfo> {
+        self.processes
+            .iter()
+            .map(|process| process.info.lock().unwrap().clone())
+            .collect()
+    }
+
+    /// Gets the processes that are still running
+    pub fn running(&self) -> Vec<ProcessInfo> {
+        self.processes()
+            .into_iter()
+            .filter(|process| process.is_running())
+            .collect()
+    }
+
+    pub fn get(&self, id: u32) -> Option<ProcessInfo> {
+        self.processes
+            .iter()
+            .map(|process| process.info.lock().unwrap().clone())
+            .find(|process| process.id == id)
+    }
+
+    /// Gets the running editor that has `project_name` open, if any
+    pub fn running_editor(&self, project_name: &str) -> Option<ProcessInfo> {
+        self.running().into_iter().find(|process| {
+            process.kind == ProcessKind::Editor
+                && process.project_name.as_deref() == Some(project_name)
+        })
+    }
+
+    /// Kills the running process with `id`. Its exit is recorded like any other exit, but it is not marked as crashed.
+    pub fn stop(&mut self, id: u32) -> Result<(), String> {
+        let process = self
+            .processes
+            .iter()
+            .find(|process| {
+                let info = process.info.lock().unwrap();
+                info.id == id && info.is_running()
+            })
+            .ok_or(format!("Did not find a running process with id {}", id))?;
+
+        // The child may have exited while its output is still being read, so its pid can't be killed anymore
+        let mut child = process.child.lock().unwrap();
+        if let Ok(Some(_)) = child.try_wait() {
+            return Err(format!("Process {} already exited", id));
+        }
+
+        *process.stopped.lock().unwrap() = true;
+        child
+            .kill()
+            .map_err(|error| format!("Could not stop process {}: {}", id, error))
+    }
+
+    /// Forgets the processes that exited
+    pub fn clear_exited(&mut self) {
+        self.processes
+            .retain(|process| process.info.lock().unwrap().is_running());
+    }
+}
+
+/// Waits for the output readers to finish, but at most `READER_TIMEOUT`. Readers that are still blocked
+/// on a pipe held open by another process are left running in the background.
+fn join_readers(readers: Vec<JoinHandle<()>>) {
+    let deadline = Instant::now() + READER_TIMEOUT;
+
+    while readers.iter().any(|reader| !reader.is_finished()) && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    for reader in readers.into_iter().filter(|reader| reader.is_finished()) {
+        reader.join().ok();
+    }
+}
+
+/// Reads lines from `stream` until it closes, writing each one to the log file and passing it to `listener`
+fn read_output(
+    stream: impl Read + Send + 'static,
+    id: u32,
+    output_stream: OutputStream,
+    log_file: Arc<Mutex<File>>,
+    listener: Arc<dyn ProcessListener>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        let mut buffer: Vec<u8> = vec![];
+
+        while reader.read_until(b'\n', &mut buffer).unwrap_or(0) > 0 {
+            let line = String::from_utf8_lossy(&buffer).trim_end().to_string();
+            buffer.clear();
+
+            writeln!(log_file.lock().unwrap(), "{}", line).ok();
+            listener.output(&ProcessOutput {
+                id,
+                stream: output_stream.clone(),
+                line,
+            });
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        path::PathBuf,
+        process::Command,
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use super::{
+        NoListener, ProcessInfo, ProcessKind, ProcessListener, ProcessOutput, ProcessRegistry,
+    };
+
+    struct RecordingListener {
+        lines: Mutex<Vec<String>>,
+        exits: Mutex<Vec<ProcessInfo>>,
+    }
+
+    impl ProcessListener for RecordingListener {
+        fn output(&self, output: &ProcessOutput) {
+            self.lines.lock().unwrap().push(output.line.clone());
+        }
+
+        fn exited(&self, process: &ProcessInfo) {
+            self.exits.lock().unwrap().push(process.clone());
+        }
+    }
+
+    fn shell(script: &str) -> Command {
+        if cfg!(target_os = "windows") {
+            let mut command = Command::new("cmd");
+            command.args(["/C", script]);
+            command
+        } else {
+            let mut command = Command::new("sh");
+            command.args(["-c", script]);
+            command
+        }
+    }
+
+    fn wait_for_exit(registry: &ProcessRegistry, id: u32) -> ProcessInfo {
+        for _ in 0..100 {
+            let process = registry.get(id).unwrap();
+            if !process.is_running() {
+                return process;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        panic!("Process {} did not exit", id);
+    }
+
+    #[test]
+    fn test_capture_output_and_exit_code() {
+        let logs_path = PathBuf::from("./test-data-process-output");
+        let listener = Arc::new(RecordingListener {
+            lines: Mutex::new(vec![]),
+            exits: Mutex::new(vec![]),
+        });
+
+        let mut registry = ProcessRegistry::new();
+        let process = registry
+            .spawn(
+                shell("echo hello && exit 3"),
+                Some("my_game"),
+                "Godot_v4.2.1-stable_win64",
+                ProcessKind::Game,
+                &logs_path,
+                listener.clone(),
+            )
+            .unwrap();
+
+        let exited = wait_for_exit(&registry, process.id);
+
+        assert!(exited.exit_code == Some(3));
+        assert!(exited.crashed);
+        assert!(listener.lines.lock().unwrap()[0] == "hello");
+        assert!(listener.exits.lock().unwrap().len() == 1);
+        assert!(fs::read_to_string(&exited.log_path)
+            .unwrap()
+            .starts_with("hello"));
+
+        registry.clear_exited();
+        assert!(registry.processes().is_empty());
+
+        fs::remove_dir_all(logs_path).unwrap();
+    }
+
+    #[test]
+    fn test_exit_with_pipes_held_open() {
+        let logs_path = PathBuf::from("./test-data-process-pipes");
+        // The started process inherits the pipes and keeps them open after the shell exited
+        let script = if cfg!(target_os = "windows") {
+            "start /b ping -n 5 127.0.0.1 && exit 0"
+        } else {
+            "sleep 5 & exit 0"
+        };
+
+        let mut registry = ProcessRegistry::new();
+        let process = registry
+            .spawn(
+                shell(script),
+                None,
+                "Godot_v4.2.1-stable_win64",
+                ProcessKind::Editor,
+                &logs_path,
+                Arc::new(NoListener),
+            )
+            .unwrap();
+
+        let exited = wait_for_exit(&registry, process.id);
+
+        assert!(exited.exit_code == Some(0));
+        assert!(!exited.crashed);
+        assert!(registry.stop(process.id).is_err());
+
+        fs::remove_dir_all(logs_path).ok();
+    }
+
+    #[test]
+    fn test_stop_process() {
+        let logs_path = PathBuf::from("./test-data-process-stop");
+        let script = if cfg!(target_os = "windows") {
+            "ping -n 30 127.0.0.1"
+        } else {
+            "exec sleep 30"
+        };
+
+        let mut registry = ProcessRegistry::new();
+        let process = registry
+            .spawn(
+                shell(script),
+                None,
+                "Godot_v4.2.1-stable_win64",
+                ProcessKind::Editor,
+                &logs_path,
+                Arc::new(NoListener),
+            )
+            .unwrap();
+
+        assert!(registry.running().len() == 1);
+        assert!(registry.running_editor("my_game").is_none());
+
+        registry.stop(process.id).unwrap();
+        let exited = wait_for_exit(&registry, process.id);
+
+        assert!(!exited.crashed);
+        assert!(registry.running().is_empty());
+        assert!(registry.stop(process.id).is_err());
+
+        fs::remove_dir_all(logs_path).unwrap();
+    }
+}
@@ -0,0 +1,132 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::project_file::ProjectFile;
+
+/// An export preset from a project's `export_presets.cfg`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExportPreset {
+    pub index: usize,
+    pub name: String,
+    pub platform: String,
+    /// The path set in the export dialog, relative to the project folder. Empty if it was never set.
+    #[serde(rename(serialize = "exportPath", deserialize = "exportPath"))]
+    pub export_path: String,
+    pub runnable: bool,
+}
+
+/// Reads the export presets of the project in `project_directory`, ordered like in the export dialog.
+/// Returns no presets if the project has no `export_presets.cfg`.
+pub fn read_export_presets(project_directory: &Path) -> Vec<ExportPreset> {
+    match ProjectFile::from_path(&project_directory.join("export_presets.cfg")) {
+        Some(presets_file) => parse_export_presets(&presets_file),
+        None => vec![],
+    }
+}
+
+/// Gets the presets from the `[preset.N]` sections of a parsed `export_presets.cfg`
+pub fn parse_export_presets(presets_file: &ProjectFile) -> Vec<ExportPreset> {
+    let mut presets: Vec<ExportPreset> = presets_file
+        .sections()
+        .iter()
+        .filter_map(|section| {
+            let index = section.strip_prefix("preset.")?.parse::<usize>().ok()?;
+
+            Some(ExportPreset {
+                index,
+                name: presets_file.get_string(section, "name")?,
+                platform: presets_file
+                    .get_string(section, "platform")
+                    .unwrap_or_default(),
+                export_path: presets_file
+                    .get_string(section, "export_path")
+                    .unwrap_or_default(),
+                runnable: presets_file.get(section, "runnable") == Some("true"),
+            })
+        })
+        .collect();
+
+    presets.sort_by_key(|preset| preset.index);
+    presets
+}
+
+/// Gets the file a preset exports to. Uses `output_path` when given, otherwise the preset's own export path.
+/// Relative paths are resolved against the project folder, since Godot runs the export from there.
+pub fn resolve_export_path(
+    project_directory: &Path,
+    preset: &ExportPreset,
+    output_path: Option<&str>,
+) -> Result<PathBuf, String> {
+    match output_path {
+        Some(output_path) => Ok(project_directory.join(output_path)),
+        None if !preset.export_path.is_empty() => Ok(project_directory.join(&preset.export_path)),
+        None => Err(format!(
+            "The preset {} has no export path, set one in the export dialog or pass an output path",
+            preset.name
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::project::project_file::ProjectFile;
+
+    use super::{parse_export_presets, resolve_export_path};
+
+    const EXPORT_PRESETS: &str = r#"[preset.0]
+
+name="Windows Desktop"
+platform="Windows Desktop"
+runnable=true
+export_filter="all_resources"
+export_path="build/windows/game.exe"
+
+[preset.0.options]
+
+custom_template/debug=""
+binary_format/embed_pck=false
+
+[preset.1]
+
+name="Web"
+platform="Web"
+runnable=false
+export_path=""
+"#;
+
+    #[test]
+    fn test_parse_export_presets() {
+        let presets = parse_export_presets(&ProjectFile::parse(EXPORT_PRESETS));
+
+        assert!(presets.len() == 2);
+        assert!(presets[0].name == "Windows Desktop");
+        assert!(presets[0].export_path == "build/windows/game.exe");
+        assert!(presets[0].runnable);
+        assert!(presets[1].name == "Web");
+        assert!(!presets[1].runnable);
+    }
+
+    #[test]
+    fn test_resolve_export_path() {
+        let presets = parse_export_presets(&ProjectFile::parse(EXPORT_PRESETS));
+        let project_directory = Path::new("./my_game");
+
+        assert!(
+            resolve_export_path(project_directory, &presets[0], None).unwrap()
+                == project_directory.join("build/windows/game.exe")
+        );
+        assert!(
+            resolve_export_path(project_directory, &presets[1], Some("web/index.html")).unwrap()
+                == project_directory.join("web/index.html")
+        );
+        assert!(
+            resolve_export_path(project_directory, &presets[1], Some("/tmp/web/index.html"))
+                .unwrap()
+                == Path::new("/tmp/web/index.html")
+        );
+        assert!(resolve_export_path(project_directory, &presets[1], None).is_err());
+    }
+}
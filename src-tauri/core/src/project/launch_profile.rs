@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::project_data::ProjectData;
+
+pub const DEFAULT_PROFILE_NAME: &str = "Editor";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum LaunchMode {
+    /// Opens the project in the editor (`--editor`)
+    Editor,
+    /// Runs the project's main scene
+    Game,
+}
+
+/// A named set of options used to launch a project, e.g. `--verbose` or `--rendering-driver opengl3`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LaunchProfile {
+    pub name: String,
+    pub mode: LaunchMode,
+    pub arguments: Vec<String>,
+    #[serde(rename(
+        serialize = "environmentVariables",
+        deserialize = "environmentVariables"
+    ))]
+    pub environment_variables: HashMap<String, String>,
+    /// The folder the engine is started in. Defaults to the project folder when empty.
+    #[serde(
+        rename(serialize = "workingDirectory", deserialize = "workingDirectory"),
+        default
+    )]
+    pub working_directory: Option<String>,
+}
+
+impl LaunchProfile {
+    /// The profile used for projects that have none configured, which opens the editor without extra arguments
+    pub fn default_editor() -> LaunchProfile {
+        LaunchProfile {
+            name: DEFAULT_PROFILE_NAME.to_string(),
+            mode: LaunchMode::Editor,
+            arguments: vec![],
+            environment_variables: HashMap::new(),
+            working_directory: None,
+        }
+    }
+}
+
+/// Gets the profile `profile_name` of a project. Falls back to the project's default profile when no name is given,
+/// and to the built-in editor profile when the project has no default.
+pub fn resolve_launch_profile(
+    project: &ProjectData,
+    profile_name: Option<&str>,
+) -> Result<LaunchProfile, String> {
+    let name = profile_name.or(project.default_launch_profile.as_deref());
+
+    match name {
+        Some(name) => project
+            .launch_profiles
+            .iter()
+            .find(|profile| profile.name == name)
+            .cloned()
+            .or_else(|| (name == DEFAULT_PROFILE_NAME).then(LaunchProfile::default_editor))
+            .ok_or(format!(
+                "Did not find a launch profile with name {} for project {}",
+                name, project.project_name
+            )),
+        None => Ok(LaunchProfile::default_editor()),
+    }
+}
+
+/// Adds `profile` to the project, replacing any existing profile with the same name
+pub fn save_launch_profile(project: &mut ProjectData, profile: LaunchProfile) {
+    match project
+        .launch_profiles
+        .iter_mut()
+        .find(|existing| existing.name == profile.name)
+    {
+        Some(existing) => *existing = profile,
+        None => project.launch_profiles.push(profile),
+    }
+}
+
+/// Removes the profile `profile_name` from the project, clearing the default if it pointed at that profile
+pub fn remove_launch_profile(project: &mut ProjectData, profile_name: &str) {
+    project
+        .launch_profiles
+        .retain(|profile| profile.name != profile_name);
+
+    if project.default_launch_profile.as_deref() == Some(profile_name) {
+        project.default_launch_profile = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::Local;
+
+    use crate::project::{
+        launch_profile::{
+            remove_launch_profile, resolve_launch_profile, save_launch_profile, LaunchMode,
+            LaunchProfile, DEFAULT_PROFILE_NAME,
+        },
+        project_data::ProjectData,
+    };
+
+    fn verbose_profile() -> LaunchProfile {
+        LaunchProfile {
+            name: "Verbose".to_string(),
+            mode: LaunchMode::Game,
+            arguments: vec!["--verbose".to_string()],
+            environment_variables: HashMap::from([(
+                "DOTNET_ROOT".to_string(),
+                "C:\\dotnet".to_string(),
+            )]),
+            working_directory: None,
+        }
+    }
+
+    #[test]
+    fn test_resolve_launch_profile() {
+        let mut project = ProjectData::new(
+            "test/project.godot".to_string(),
+            "".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        );
+
+        assert!(resolve_launch_profile(&project, None).unwrap().name == DEFAULT_PROFILE_NAME);
+        assert!(resolve_launch_profile(&project, Some("Verbose")).is_err());
+
+        save_launch_profile(&mut project, verbose_profile());
+        project.default_launch_profile = Some("Verbose".to_string());
+
+        assert!(resolve_launch_profile(&project, None).unwrap() == verbose_profile());
+        assert!(
+            resolve_launch_profile(&project, Some(DEFAULT_PROFILE_NAME))
+                .unwrap()
+                .mode
+                == LaunchMode::Editor
+        );
+    }
+
+    #[test]
+    fn test_save_and_remove_launch_profile() {
+        let mut project = ProjectData::new(
+            "test/project.godot".to_string(),
+            "".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        );
+
+        save_launch_profile(&mut project, verbose_profile());
+        let mut updated = verbose_profile();
+        updated.arguments.push("--debug-collisions".to_string());
+        save_launch_profile(&mut project, updated);
+
+        assert!(project.launch_profiles.len() == 1);
+        assert!(project.launch_profiles[0].arguments.len() == 2);
+
+        project.default_launch_profile = Some("Verbose".to_string());
+        remove_launch_profile(&mut project, "Verbose");
+
+        assert!(project.launch_profiles.is_empty());
+        assert!(project.default_launch_profile.is_none());
+    }
+}
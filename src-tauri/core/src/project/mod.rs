@@ -0,0 +1,13 @@
+pub mod export_preset;
+pub mod launch_profile;
+pub mod project_backup;
+pub mod project_cache;
+pub mod project_data;
+pub mod project_diagnostics;
+pub mod project_file;
+pub mod project_launcher;
+pub mod project_query;
+pub mod project_service;
+pub mod project_thumbnail;
+pub mod project_upgrade;
+pub mod usage_history;
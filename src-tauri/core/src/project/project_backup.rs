@@ -0,0 +1,655 @@
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    error::Error,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::process::process_log::sanitize_name;
+
+use super::{project_data::ProjectData, project_service::project_directory};
+
+/// Folders at the root of a project that are never backed up, Godot regenerates them on import
+pub const DEFAULT_EXCLUDES: [&str; 2] = [".godot", ".import"];
+
+/// Version control folders, at any depth, that are never backed up and never touched by a restore, so restoring
+/// a backup doesn't lose the commits made since
+pub const VERSION_CONTROL_FOLDERS: [&str; 3] = [".git", ".hg", ".svn"];
+
+/// How many backups of a project are kept when no retention is configured
+pub const DEFAULT_KEEP_LAST: usize = 10;
+
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+/// Which files are left out of backups and how long backups are kept.
+/// A backup is kept while it is one of the last `keep_last` backups of its project or younger than `keep_days`.
+/// Without either rule all backups are kept.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct BackupSettings {
+    /// Paths relative to the project to leave out, e.g. `exports/**` or `*.blend1`.
+    /// A pattern without a `/` matches a file or folder name anywhere in the project.
+    #[serde(rename(serialize = "excludeGlobs", deserialize = "excludeGlobs"))]
+    pub exclude_globs: Vec<String>,
+    #[serde(rename(serialize = "keepLast", deserialize = "keepLast"))]
+    pub keep_last: Option<usize>,
+    #[serde(rename(serialize = "keepDays", deserialize = "keepDays"))]
+    pub keep_days: Option<u32>,
+}
+
+impl Default for BackupSettings {
+    fn default() -> Self {
+        BackupSettings {
+            exclude_globs: vec![],
+            keep_last: Some(DEFAULT_KEEP_LAST),
+            keep_days: None,
+        }
+    }
+}
+
+/// A snapshot of a project. It is stored as `<id>.zip` with this manifest next to it as `<id>.json`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProjectBackup {
+    pub id: String,
+    #[serde(rename(serialize = "projectName", deserialize = "projectName"))]
+    pub project_name: String,
+    #[serde(rename(serialize = "projectPath", deserialize = "projectPath"))]
+    pub project_path: String,
+    /// The engine assigned to the project when the backup was made
+    #[serde(rename(serialize = "engineVersion", deserialize = "engineVersion"))]
+    pub engine_version: String,
+    #[serde(rename(serialize = "createdAt", deserialize = "createdAt"))]
+    pub created_at: i64,
+    #[serde(rename(serialize = "archivePath", deserialize = "archivePath"))]
+    pub archive_path: String,
+    pub size: u64,
+    /// The exclude globs the backup was made with, whose files are kept when it is restored
+    #[serde(
+        rename(serialize = "excludeGlobs", deserialize = "excludeGlobs"),
+        default
+    )]
+    pub exclude_globs: Vec<String>,
+}
+
+/// Zips `project` into `backups_path/<project name>/`, leaving out `DEFAULT_EXCLUDES`, `VERSION_CONTROL_FOLDERS`
+/// and the exclude globs of `settings`. Older backups of the project that fall outside the retention of `settings` are deleted afterwards.
+pub fn create_backup(
+    project: &ProjectData,
+    backups_path: &Path,
+    settings: &BackupSettings,
+) -> Result<ProjectBackup, Box<dyn Error>> {
+    let directory = backups_path.join(sanitize_name(&project.project_name));
+    fs::create_dir_all(&directory)?;
+
+    let now = Local::now();
+    let id = now.format("%Y-%m-%d_%H-%M-%S%.3f").to_string();
+    let archive_path = directory.join(format!("{}.zip", id));
+
+    let mut archive = ZipWriter::new(File::create(&archive_path)?);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (path, name) in backup_entries(
+        &project_directory(&project.project_path),
+        &settings.exclude_globs,
+    ) {
+        if path.is_dir() {
+            archive.add_directory(name, options)?;
+        } else {
+            archive.start_file(name, options)?;
+            io::copy(&mut File::open(&path)?, &mut archive)?;
+        }
+    }
+
+    archive.finish()?.flush()?;
+
+    let backup = ProjectBackup {
+        id,
+        project_name: project.project_name.clone(),
+        project_path: project.project_path.clone(),
+        engine_version: project.engine_version.clone(),
+        created_at: now.timestamp_millis(),
+        archive_path: archive_path.to_string_lossy().to_string(),
+        size: fs::metadata(&archive_path)?.len(),
+        exclude_globs: settings.exclude_globs.clone(),
+    };
+
+    fs::write(
+        archive_path.with_extension("json"),
+        serde_json::to_string_pretty(&backup)?,
+    )?;
+
+    apply_retention(
+        backups_path,
+        &project.project_name,
+        settings,
+        backup.created_at,
+    )?;
+
+    Ok(backup)
+}
+
+/// Lists the backups of a project, newest first. Backups whose archive is missing are skipped.
+pub fn list_backups(backups_path: &Path, project_name: &str) -> Vec<ProjectBackup> {
+    let Ok(entries) = fs::read_dir(backups_path.join(sanitize_name(project_name))) else {
+        return vec![];
+    };
+
+    let mut backups: Vec<ProjectBackup> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .filter_map(|path| {
+            let contents = fs::read_to_string(&path).ok()?;
+            let mut backup: ProjectBackup = serde_json::from_str(&contents).ok()?;
+
+            // The storage folder may have moved since the backup was made
+            let archive_path = path.with_extension("zip");
+            backup.size = fs::metadata(&archive_path).ok()?.len();
+            backup.archive_path = archive_path.to_string_lossy().to_string();
+
+            Some(backup)
+        })
+        .collect();
+
+    backups.sort_by_key(|backup| Reverse(backup.created_at));
+    backups
+}
+
+pub fn find_backup(
+    backups_path: &Path,
+    project_name: &str,
+    id: &str,
+) -> Result<ProjectBackup, String> {
+    list_backups(backups_path, project_name)
+        .into_iter()
+        .find(|backup| backup.id == id)
+        .ok_or(format!(
+            "Did not find a backup with id {} for project {}",
+            id, project_name
+        ))
+}
+
+/// Restores `project_directory` to the state of `backup`. Files in the backup overwrite the current ones,
+/// and files created since are deleted together with the `DEFAULT_EXCLUDES` caches, e.g. the `.godot` folder
+/// of a failed conversion. Files matching the exclude globs of the backup were never saved, so they are kept,
+/// and so are `VERSION_CONTROL_FOLDERS`. The backup is extracted next to the project first and only moved in
+/// once it was extracted completely, so a broken archive leaves the project as it was.
+pub fn restore_backup(
+    backup: &ProjectBackup,
+    project_directory: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let archive = ZipArchive::new(File::open(&backup.archive_path)?)?;
+    let archived: HashSet<String> = archive.file_names().map(|name| name.to_string()).collect();
+
+    fs::create_dir_all(project_directory)?;
+
+    // A sibling of the project is on the same drive, so moving the extracted files in is a rename
+    let extract_directory = project_directory.with_file_name(format!(
+        ".{}_restore_{}",
+        project_directory
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        Local::now().timestamp_millis()
+    ));
+    let extracted =
+        zip_extract::extract(File::open(&backup.archive_path)?, &extract_directory, false)
+            .map_err(|error| error.into())
+            .and_then(|_| move_entries(&extract_directory, project_directory));
+    fs::remove_dir_all(&extract_directory).ok();
+    extracted?;
+
+    for excluded in DEFAULT_EXCLUDES {
+        let path = project_directory.join(excluded);
+        if path.is_dir() {
+            fs::remove_dir_all(path)?;
+        }
+    }
+
+    // Children come after their folder, so going backwards empties folders before removing them.
+    // Folders that still hold excluded files are kept.
+    for (path, name) in backup_entries(project_directory, &backup.exclude_globs)
+        .into_iter()
+        .rev()
+    {
+        if archived.contains(&name) {
+            continue;
+        }
+
+        if name.ends_with('/') {
+            fs::remove_dir(path).ok();
+        } else {
+            fs::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Moves the files extracted into `from` to the same place in `to`, replacing the files there
+fn move_entries(from: &Path, to: &Path) -> Result<(), Box<dyn Error>> {
+    for (path, name) in backup_entries(from, &[]) {
+        let target = to.join(&name);
+
+        if name.ends_with('/') {
+            fs::create_dir_all(target)?;
+        } else {
+            fs::rename(path, target)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn delete_backup(backup: &ProjectBackup) -> io::Result<()> {
+    let archive_path = PathBuf::from(&backup.archive_path);
+
+    fs::remove_file(&archive_path)?;
+    fs::remove_file(archive_path.with_extension("json"))
+}
+
+/// Deletes the backups of a project that fall outside the retention of `settings`, counting days from `now`
+/// (in milliseconds). The newest backup is always kept. Returns the deleted backups.
+pub fn apply_retention(
+    backups_path: &Path,
+    project_name: &str,
+    settings: &BackupSettings,
+    now: i64,
+) -> io::Result<Vec<ProjectBackup>> {
+    if settings.keep_last.is_none() && settings.keep_days.is_none() {
+        return Ok(vec![]);
+    }
+
+    let mut deleted: Vec<ProjectBackup> = vec![];
+
+    for (index, backup) in list_backups(backups_path, project_name)
+        .into_iter()
+        .enumerate()
+    {
+        let within_last = index == 0
+            || settings
+                .keep_last
+                .is_some_and(|keep_last| index < keep_last);
+        let within_days = settings
+            .keep_days
+            .is_some_and(|keep_days| backup.created_at >= now - keep_days as i64 * DAY_MILLIS);
+
+        if !within_last && !within_days {
+            delete_backup(&backup)?;
+            deleted.push(backup);
+        }
+    }
+
+    Ok(deleted)
+}
+
+/// Matches a path relative to the project, using `/` as separator, against a glob pattern.
+/// `*` and `?` don't match across folders, `**` does.
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_start_matches('/').trim_end_matches('/');
+
+    if !pattern.contains('/') {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        return matches_chars(
+            &pattern.chars().collect::<Vec<char>>(),
+            &name.chars().collect::<Vec<char>>(),
+        );
+    }
+
+    matches_chars(
+        &pattern.chars().collect::<Vec<char>>(),
+        &path.chars().collect::<Vec<char>>(),
+    )
+}
+
+fn matches_chars(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // `**/` also matches no folder at all
+            (rest.first() == Some(&'/') && matches_chars(&rest[1..], path))
+                || (0..=path.len()).any(|index| matches_chars(rest, &path[index..]))
+        }
+        ['*', rest @ ..] => (0..=path.len())
+            .take_while(|index| *index == 0 || path[index - 1] != '/')
+            .any(|index| matches_chars(rest, &path[index..])),
+        ['?', rest @ ..] => {
+            path.first().is_some_and(|character| *character != '/')
+                && matches_chars(rest, &path[1..])
+        }
+        [character, rest @ ..] => {
+            path.first() == Some(character) && matches_chars(rest, &path[1..])
+        }
+    }
+}
+
+/// Lists the files and folders of a project that go into a backup, paired with their name in the archive.
+/// Names always use `/` so backups made on Windows can be restored elsewhere. The contents of excluded folders and
+/// version control folders are skipped, and so are symbolic links, which can point outside the project or back at
+/// one of its parents.
+fn backup_entries(project_directory: &Path, exclude_globs: &[String]) -> Vec<(PathBuf, String)> {
+    let mut entries: Vec<(PathBuf, String)> = vec![];
+    let mut pending: Vec<PathBuf> = vec![project_directory.to_path_buf()];
+
+    while let Some(directory) = pending.pop() {
+        let Ok(children) = fs::read_dir(&directory) else {
+            continue;
+        };
+
+        for child in children.filter_map(|child| child.ok()) {
+            let path = child.path();
+            let relative = path.strip_prefix(project_directory).unwrap();
+            let components: Vec<String> = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().to_string())
+                .collect();
+
+            if DEFAULT_EXCLUDES.contains(&components[0].as_str())
+                || VERSION_CONTROL_FOLDERS.contains(&components[components.len() - 1].as_str())
+            {
+                continue;
+            }
+
+            let name = components.join("/");
+            if exclude_globs
+                .iter()
+                .any(|pattern| glob_matches(pattern, &name))
+            {
+                continue;
+            }
+
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                continue;
+            };
+            if metadata.file_type().is_symlink() {
+                continue;
+            }
+
+            if metadata.is_dir() {
+                pending.push(path.clone());
+                entries.push((path, format!("{}/", name)));
+            } else {
+                entries.push((path, name));
+            }
+        }
+    }
+
+    entries.sort_by(|a, b| a.1.cmp(&b.1));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, fs::File, path::PathBuf};
+
+    use chrono::Local;
+    use zip::ZipArchive;
+
+    use crate::project::project_data::ProjectData;
+
+    use super::{
+        apply_retention, create_backup, delete_backup, find_backup, glob_matches, list_backups,
+        restore_backup, BackupSettings, DAY_MILLIS,
+    };
+
+    fn project(path: &str) -> ProjectData {
+        ProjectData::new(
+            format!("{}/project.godot", path),
+            "Godot_v3.5.3-stable_win64".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        )
+    }
+
+    #[test]
+    fn test_create_and_restore_backup() {
+        let path = PathBuf::from("./test-data-backup");
+        let project_directory = path.join("my_game");
+        fs::create_dir_all(project_directory.join("scenes")).unwrap();
+        fs::create_dir_all(project_directory.join(".godot").join("imported")).unwrap();
+        fs::create_dir_all(project_directory.join(".import")).unwrap();
+        fs::create_dir_all(project_directory.join("exports")).unwrap();
+        fs::write(
+            project_directory.join("project.godot"),
+            "config_version=4\n",
+        )
+        .unwrap();
+        fs::write(project_directory.join("scenes").join("main.tscn"), "old").unwrap();
+        fs::write(project_directory.join("scenes").join("main.tscn~"), "").unwrap();
+        fs::write(project_directory.join("exports").join("game.exe"), "").unwrap();
+        fs::create_dir_all(project_directory.join(".git").join("objects")).unwrap();
+        fs::write(project_directory.join(".git").join("HEAD"), "old").unwrap();
+        fs::write(
+            project_directory
+                .join(".godot")
+                .join("imported")
+                .join("icon.ctex"),
+            "",
+        )
+        .unwrap();
+
+        let settings = BackupSettings {
+            exclude_globs: vec!["exports".to_string(), "*~".to_string()],
+            keep_last: None,
+            keep_days: None,
+        };
+        let backups_path = path.join("backups");
+        let backup = create_backup(
+            &project("./test-data-backup/my_game"),
+            &backups_path,
+            &settings,
+        )
+        .unwrap();
+
+        assert!(backup.engine_version == "Godot_v3.5.3-stable_win64");
+
+        let archive = ZipArchive::new(File::open(&backup.archive_path).unwrap()).unwrap();
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort();
+
+        assert!(names == vec!["project.godot", "scenes/", "scenes/main.tscn"]);
+
+        let backups = list_backups(&backups_path, "my_game");
+        assert!(backups.len() == 1);
+        assert!(backups[0].id == backup.id);
+
+        // A failed conversion changes files, adds new ones and imports the project again
+        fs::write(project_directory.join("scenes").join("main.tscn"), "new").unwrap();
+        fs::write(project_directory.join("scenes").join("main.gd"), "").unwrap();
+        fs::create_dir_all(project_directory.join("addons").join("plugin")).unwrap();
+        fs::write(
+            project_directory
+                .join("addons")
+                .join("plugin")
+                .join("plugin.gd"),
+            "",
+        )
+        .unwrap();
+        fs::write(project_directory.join(".godot").join("uid_cache.bin"), "").unwrap();
+        // Commits made since the backup
+        fs::write(project_directory.join(".git").join("HEAD"), "new").unwrap();
+        fs::write(
+            project_directory.join(".git").join("objects").join("ab12"),
+            "",
+        )
+        .unwrap();
+
+        let found = find_backup(&backups_path, "my_game", &backup.id).unwrap();
+        assert!(found.exclude_globs == settings.exclude_globs);
+        restore_backup(&found, &project_directory).unwrap();
+
+        assert!(
+            fs::read_to_string(project_directory.join("scenes").join("main.tscn")).unwrap()
+                == "old"
+        );
+        assert!(!project_directory.join("scenes").join("main.gd").exists());
+        assert!(!project_directory.join("addons").exists());
+        assert!(!project_directory.join(".godot").exists());
+        assert!(!project_directory.join(".import").exists());
+        // Excluded files were not in the backup, so they are kept
+        assert!(project_directory.join("exports").join("game.exe").exists());
+        assert!(project_directory.join("scenes").join("main.tscn~").exists());
+        // Version control folders are neither saved nor touched
+        assert!(fs::read_to_string(project_directory.join(".git").join("HEAD")).unwrap() == "new");
+        assert!(project_directory
+            .join(".git")
+            .join("objects")
+            .join("ab12")
+            .exists());
+        // The backup was extracted next to the project and moved in
+        assert!(fs::read_dir(&path)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .all(|entry| !entry.file_name().to_string_lossy().contains("_restore_")));
+
+        delete_backup(&found).unwrap();
+        assert!(list_backups(&backups_path, "my_game").is_empty());
+        assert!(find_backup(&backups_path, "my_game", &backup.id).is_err());
+
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn test_restore_broken_backup() {
+        let path = PathBuf::from("./test-data-backup-broken");
+        let project_directory = path.join("my_game");
+        fs::create_dir_all(&project_directory).unwrap();
+        fs::write(
+            project_directory.join("project.godot"),
+            "config_version=5\n".repeat(100),
+        )
+        .unwrap();
+        fs::write(project_directory.join("main.gd"), "").unwrap();
+
+        let backup = create_backup(
+            &project("./test-data-backup-broken/my_game"),
+            &path.join("backups"),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+
+        // Damage the compressed contents of project.godot but keep the list of files readable
+        let mut bytes = fs::read(&backup.archive_path).unwrap();
+        let contents = bytes
+            .windows("project.godot".len())
+            .position(|window| window == b"project.godot")
+            .unwrap()
+            + "project.godot".len();
+        for byte in &mut bytes[contents + 2..contents + 12] {
+            *byte = !*byte;
+        }
+        fs::write(&backup.archive_path, bytes).unwrap();
+        fs::remove_file(project_directory.join("main.gd")).unwrap();
+        fs::write(project_directory.join("player.gd"), "").unwrap();
+
+        assert!(restore_backup(&backup, &project_directory).is_err());
+        assert!(project_directory.join("player.gd").exists());
+        assert!(!project_directory.join("main.gd").exists());
+        assert!(fs::read_dir(&path).unwrap().count() == 2);
+
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_backup_skips_symlinks() {
+        let path = PathBuf::from("./test-data-backup-symlinks");
+        let project_directory = path.join("my_game");
+        fs::create_dir_all(&project_directory).unwrap();
+        fs::create_dir_all(path.join("shared_addons")).unwrap();
+        fs::write(project_directory.join("project.godot"), "").unwrap();
+        fs::write(path.join("shared_addons").join("plugin.gd"), "").unwrap();
+
+        let absolute = fs::canonicalize(&path).unwrap();
+        std::os::unix::fs::symlink(
+            absolute.join("shared_addons"),
+            project_directory.join("addons"),
+        )
+        .unwrap();
+        std::os::unix::fs::symlink(&absolute, project_directory.join("parent")).unwrap();
+
+        let backup = create_backup(
+            &project("./test-data-backup-symlinks/my_game"),
+            &path.join("backups"),
+            &BackupSettings::default(),
+        )
+        .unwrap();
+
+        let archive = ZipArchive::new(File::open(&backup.archive_path).unwrap()).unwrap();
+        assert!(archive.file_names().collect::<Vec<&str>>() == vec!["project.godot"]);
+
+        // Restoring leaves the links and what they point to alone
+        restore_backup(&backup, &project_directory).unwrap();
+        assert!(path.join("shared_addons").join("plugin.gd").exists());
+
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn test_apply_retention() {
+        let path = PathBuf::from("./test-data-backup-retention");
+        fs::create_dir_all(path.join("my_game")).unwrap();
+        fs::write(path.join("my_game").join("project.godot"), "").unwrap();
+
+        let project = project("./test-data-backup-retention/my_game");
+        let backups_path = path.join("backups");
+        let keep_all = BackupSettings {
+            exclude_globs: vec![],
+            keep_last: None,
+            keep_days: None,
+        };
+
+        for _ in 0..4 {
+            create_backup(&project, &backups_path, &keep_all).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert!(list_backups(&backups_path, "my_game").len() == 4);
+
+        let now = Local::now().timestamp_millis();
+        let keep_days = BackupSettings {
+            keep_days: Some(1),
+            ..keep_all.clone()
+        };
+        assert!(apply_retention(&backups_path, "my_game", &keep_days, now)
+            .unwrap()
+            .is_empty());
+
+        let keep_last = BackupSettings {
+            keep_last: Some(2),
+            keep_days: Some(1),
+            ..keep_all.clone()
+        };
+        let deleted =
+            apply_retention(&backups_path, "my_game", &keep_last, now + 2 * DAY_MILLIS).unwrap();
+        let remaining = list_backups(&backups_path, "my_game");
+
+        assert!(deleted.len() == 2);
+        assert!(remaining.len() == 2);
+        assert!(remaining[0].created_at > deleted[0].created_at);
+
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("*.blend1", "models/ship.blend1"));
+        assert!(glob_matches("exports", "exports"));
+        assert!(glob_matches("exports/", "exports"));
+        assert!(glob_matches("assets/*.psd", "assets/ship.psd"));
+        assert!(!glob_matches("assets/*.psd", "assets/raw/ship.psd"));
+        assert!(glob_matches("assets/**/*.psd", "assets/raw/ship.psd"));
+        assert!(glob_matches("assets/**/*.psd", "assets/ship.psd"));
+        assert!(glob_matches("scene?.tscn", "levels/scene1.tscn"));
+        assert!(!glob_matches("*.psd", "ship.png"));
+    }
+}
@@ -0,0 +1,157 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    directory::disk_usage::directory_size,
+    process::editor_lock::{self, ProcessSnapshot},
+};
+
+use super::{project_data::ProjectData, project_service};
+
+/// Folders relative to a project that Godot regenerates when they are missing. The first open after
+/// deleting them reimports every asset and recompiles shaders, which can take a while.
+pub const CACHE_FOLDERS: [&str; 5] = [
+    ".godot/imported",
+    ".godot/shader_cache",
+    ".godot/mono/temp",
+    ".import",
+    ".mono/temp",
+];
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CacheFolder {
+    /// The folder relative to the project, e.g. `.godot/imported`
+    pub path: String,
+    pub size: u64,
+}
+
+/// The cache folders of a project that exist and their sizes
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProjectCache {
+    #[serde(rename(serialize = "projectName", deserialize = "projectName"))]
+    pub project_name: String,
+    pub folders: Vec<CacheFolder>,
+    #[serde(rename(serialize = "totalSize", deserialize = "totalSize"))]
+    pub total_size: u64,
+}
+
+/// The outcome of cleaning the cache of one project
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CacheCleanResult {
+    #[serde(rename(serialize = "projectName", deserialize = "projectName"))]
+    pub project_name: String,
+    #[serde(rename(serialize = "dryRun", deserialize = "dryRun"))]
+    pub dry_run: bool,
+    /// The bytes that were freed, or would be freed for a dry run
+    #[serde(rename(serialize = "freedBytes", deserialize = "freedBytes"))]
+    pub freed_bytes: u64,
+    /// Why the project was not cleaned, e.g. because an editor has it open
+    pub error: Option<String>,
+}
+
+pub fn project_cache(project_name: &str, project_directory: &Path) -> ProjectCache {
+    let folders: Vec<CacheFolder> = CACHE_FOLDERS
+        .iter()
+        .filter(|folder| project_directory.join(folder).is_dir())
+        .map(|folder| CacheFolder {
+            path: folder.to_string(),
+            size: directory_size(&project_directory.join(folder)),
+        })
+        .collect();
+
+    ProjectCache {
+        project_name: project_name.to_string(),
+        total_size: folders.iter().map(|folder| folder.size).sum(),
+        folders,
+    }
+}
+
+/// Deletes the cache folders of a project and returns the bytes freed. A dry run only measures them.
+/// Callers must make sure no editor has the project open, since it would write to the folders while they are deleted.
+pub fn clean_project_cache(project_directory: &Path, dry_run: bool) -> io::Result<u64> {
+    let cache = project_cache("", project_directory);
+
+    if !dry_run {
+        for folder in &cache.folders {
+            fs::remove_dir_all(project_directory.join(&folder.path))?;
+        }
+    }
+
+    Ok(cache.total_size)
+}
+
+/// Cleans the caches of `projects`, skipping projects an editor has open. `running_editors` maps the names of
+/// projects to the editors the caller itself started for them. The running processes are only read once.
+pub fn clean_project_caches(
+    projects: &[ProjectData],
+    running_editors: &HashMap<String, u32>,
+    dry_run: bool,
+) -> Vec<CacheCleanResult> {
+    let processes = ProcessSnapshot::new();
+
+    projects
+        .iter()
+        .map(|project| {
+            let project_directory = project_service::project_directory(&project.project_path);
+            let running_editor = running_editors
+                .get(&project.project_name)
+                .copied()
+                .or_else(|| editor_lock::find_locked_editor(&project_directory, &processes));
+
+            let result = match running_editor {
+                Some(id) => Err(editor_lock::already_open_message(&project.project_name, id)),
+                None => clean_project_cache(&project_directory, dry_run)
+                    .map_err(|error| format!("Could not clean cache: {}", error)),
+            };
+
+            CacheCleanResult {
+                project_name: project.project_name.clone(),
+                dry_run,
+                freed_bytes: *result.as_ref().unwrap_or(&0),
+                error: result.err(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{clean_project_cache, project_cache};
+
+    #[test]
+    fn test_clean_project_cache() {
+        let path = PathBuf::from("./test-data-project-cache");
+        fs::create_dir_all(path.join(".godot").join("imported")).unwrap();
+        fs::create_dir_all(path.join(".godot").join("editor")).unwrap();
+        fs::create_dir_all(path.join(".import")).unwrap();
+        fs::write(
+            path.join(".godot").join("imported").join("icon.ctex"),
+            vec![0u8; 100],
+        )
+        .unwrap();
+        fs::write(path.join(".import").join("icon.stex"), vec![0u8; 50]).unwrap();
+        fs::write(
+            path.join(".godot").join("editor").join("editor_layout.cfg"),
+            "",
+        )
+        .unwrap();
+
+        let cache = project_cache("my_game", &path);
+        assert!(cache.folders.len() == 2);
+        assert!(cache.total_size == 150);
+
+        assert!(clean_project_cache(&path, true).unwrap() == 150);
+        assert!(path.join(".import").exists());
+
+        assert!(clean_project_cache(&path, false).unwrap() == 150);
+        assert!(!path.join(".import").exists());
+        assert!(!path.join(".godot").join("imported").exists());
+        assert!(path.join(".godot").join("editor").exists());
+        assert!(project_cache("my_game", &path).total_size == 0);
+
+        fs::remove_dir_all(path).unwrap();
+    }
+}
@@ -3,7 +3,12 @@ use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use crate::godot_service::godot_engine_version::GodotEngineVersion;
+use crate::{
+    godot_service::{godot_engine_version::GodotEngineVersion, release_channels::ReleaseFilter},
+    news::news_cache::NewsSettings,
+};
+
+use super::{launch_profile::LaunchProfile, project_backup::BackupSettings};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ProjectData {
@@ -19,6 +24,41 @@ pub struct ProjectData {
     pub path_valid: bool,
     #[serde(rename(serialize = "engineValid", deserialize = "engineValid"))]
     pub engine_valid: bool,
+    #[serde(rename(serialize = "isCSharp", deserialize = "isCSharp"), default)]
+    pub is_csharp: bool,
+    #[serde(
+        rename(serialize = "launchProfiles", deserialize = "launchProfiles"),
+        default
+    )]
+    pub launch_profiles: Vec<LaunchProfile>,
+    #[serde(
+        rename(
+            serialize = "defaultLaunchProfile",
+            deserialize = "defaultLaunchProfile"
+        ),
+        default
+    )]
+    pub default_launch_profile: Option<String>,
+    #[serde(flatten)]
+    pub metadata: ProjectMetadata,
+}
+
+/// Details about a project that are set by the user rather than read from the project itself
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProjectMetadata {
+    /// Favorite projects are pinned to the top of the project list
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub note: String,
+    /// A CSS color used to label the project, e.g. `#ff6b6b`
+    #[serde(rename(serialize = "colorLabel", deserialize = "colorLabel"), default)]
+    pub color_label: Option<String>,
+    /// Pinned projects keep their engine when an engine upgrade reassigns the projects using it
+    #[serde(rename(serialize = "pinEngine", deserialize = "pinEngine"), default)]
+    pub pin_engine: bool,
 }
 
 impl ProjectData {
@@ -29,10 +69,15 @@ impl ProjectData {
         path_valid: bool,
         engine_valid: bool,
     ) -> ProjectData {
-        let split: Vec<&str> = project_path.split("\\").collect();
-        let project_name = split[split.len() - 2]; // We use the folder name as the project name
-
         let path = PathBuf::from(&project_path);
+
+        // We use the folder name as the project name
+        let project_name = path
+            .parent()
+            .and_then(|parent| parent.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or(project_path.clone());
+
         if !path.exists() {
             println!(
                 "Path {:?} does is not valid for project {}",
@@ -47,6 +92,10 @@ impl ProjectData {
             last_date_opened,
             path_valid,
             engine_valid,
+            is_csharp: false,
+            launch_profiles: vec![],
+            default_launch_profile: None,
+            metadata: ProjectMetadata::default(),
         }
     }
 }
@@ -56,6 +105,22 @@ pub struct ProjectConfig {
     pub tracked_directories: Vec<String>,
     pub tracked_projects: Vec<ProjectData>,
     pub tracked_godot_versions: Vec<GodotEngineVersion>,
+    #[serde(
+        rename(serialize = "backupSettings", deserialize = "backupSettings"),
+        default
+    )]
+    pub backup_settings: BackupSettings,
+    /// The release channels and flavors shown in the engine catalog
+    #[serde(
+        rename(serialize = "releaseFilter", deserialize = "releaseFilter"),
+        default
+    )]
+    pub release_filter: ReleaseFilter,
+    #[serde(
+        rename(serialize = "newsSettings", deserialize = "newsSettings"),
+        default
+    )]
+    pub news_settings: NewsSettings,
 }
 
 impl fmt::Display for ProjectConfig {
@@ -74,8 +139,8 @@ impl fmt::Display for ProjectData {
         // Customize so only `x` and `y` are denoted.
         write!(
             f,
-            "project_name: {}, project_path: {}, project_version: {}, last_date_opened: {}, path_valid: {}, engine_valid: {}",
-            self.project_name, self.project_path, self.engine_version, self.last_date_opened, self.path_valid, self.engine_valid
+            "project_name: {}, project_path: {}, project_version: {}, last_date_opened: {}, path_valid: {}, engine_valid: {}, is_csharp: {}",
+            self.project_name, self.project_path, self.engine_version, self.last_date_opened, self.path_valid, self.engine_valid, self.is_csharp
         )
     }
 }
@@ -85,8 +150,8 @@ impl fmt::Debug for ProjectData {
         // Customize so only `x` and `y` are denoted.
         write!(
             f,
-            "project_name: {}, project_path: {}, project_version: {}, last_date_opened: {}, path_valid: {}, engine_valid: {}",
-            self.project_name, self.project_path, self.engine_version, self.last_date_opened, self.path_valid, self.engine_valid
+            "project_name: {}, project_path: {}, project_version: {}, last_date_opened: {}, path_valid: {}, engine_valid: {}, is_csharp: {}",
+            self.project_name, self.project_path, self.engine_version, self.last_date_opened, self.path_valid, self.engine_valid, self.is_csharp
         )
     }
 }
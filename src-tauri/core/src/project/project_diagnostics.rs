@@ -0,0 +1,366 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    directory::disk_usage::{directory_size, format_size},
+    godot_service::godot_engine_version::{
+        parse_major_minor, version_number_from_name, GodotEngineVersion,
+    },
+};
+
+use super::{project_file::ProjectFile, project_thumbnail::resolve_resource_path};
+
+/// Import folders larger than this are reported when they are not ignored by git
+pub const LARGE_IMPORT_FOLDER_BYTES: u64 = 500 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, PartialOrd)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A problem found in a project
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    #[serde(rename(serialize = "suggestedFix", deserialize = "suggestedFix"))]
+    pub suggested_fix: String,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, message: String, suggested_fix: &str) -> Diagnostic {
+        Diagnostic {
+            severity,
+            message,
+            suggested_fix: suggested_fix.to_string(),
+        }
+    }
+}
+
+/// Checks the project in `project_directory` for problems. `engine` is the engine assigned to the project and
+/// `last_engine_name` the engine the project was last opened with, if they are known.
+/// Findings are sorted with the most severe first.
+pub fn diagnose_project(
+    project_directory: &Path,
+    engine: Option<&GodotEngineVersion>,
+    last_engine_name: Option<&str>,
+) -> Vec<Diagnostic> {
+    let Some(project_file) = ProjectFile::from_path(&project_directory.join("project.godot"))
+    else {
+        return vec![Diagnostic::new(
+            Severity::Error,
+            format!("No project.godot found in {}", project_directory.display()),
+            "Remove the project from the list or restore its project.godot file.",
+        )];
+    };
+
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+
+    check_required_fields(&project_file, &mut diagnostics);
+    if let Some(engine) = engine {
+        check_engine_version(&project_file, engine, &mut diagnostics);
+        check_stale_cache(
+            project_directory,
+            engine,
+            last_engine_name,
+            &mut diagnostics,
+        );
+    }
+    check_editor_plugins(project_directory, &project_file, &mut diagnostics);
+    check_import_folders(project_directory, &mut diagnostics);
+
+    diagnostics.sort_by(|a, b| b.severity.partial_cmp(&a.severity).unwrap());
+    diagnostics
+}
+
+fn check_required_fields(project_file: &ProjectFile, diagnostics: &mut Vec<Diagnostic>) {
+    if project_file.config_version().is_none() {
+        diagnostics.push(Diagnostic::new(
+            Severity::Error,
+            "project.godot has no config_version".to_string(),
+            "Add config_version=5 for Godot 4 or config_version=4 for Godot 3 at the top of project.godot.",
+        ));
+    }
+
+    if project_file
+        .get_string("application", "config/name")
+        .is_none()
+    {
+        diagnostics.push(Diagnostic::new(
+            Severity::Warning,
+            "project.godot has no application/config/name".to_string(),
+            "Set a name in Project Settings > Application > Config.",
+        ));
+    }
+
+    if project_file
+        .get_string("application", "run/main_scene")
+        .is_none()
+    {
+        diagnostics.push(Diagnostic::new(
+            Severity::Warning,
+            "The project has no main scene, so it cannot be run".to_string(),
+            "Set a main scene in Project Settings > Application > Run.",
+        ));
+    }
+}
+
+/// Compares the Godot version in `config/features` (Godot 4) or the `config_version` (Godot 3) with the assigned engine
+fn check_engine_version(
+    project_file: &ProjectFile,
+    engine: &GodotEngineVersion,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some(engine_version) = engine.major_minor() else {
+        return;
+    };
+
+    let is_godot_3_project = project_file
+        .config_version()
+        .is_some_and(|config_version| config_version < 5);
+
+    if is_godot_3_project && engine_version.0 >= 4 {
+        diagnostics.push(Diagnostic::new(
+            Severity::Error,
+            format!(
+                "This is a Godot 3 project but it is assigned to {}",
+                engine.version_name
+            ),
+            "Assign a Godot 3 engine, or convert the project with the upgrade assistant first.",
+        ));
+        return;
+    }
+
+    if !is_godot_3_project && engine_version.0 < 4 {
+        diagnostics.push(Diagnostic::new(
+            Severity::Error,
+            format!(
+                "This is a Godot 4 project but it is assigned to {}",
+                engine.version_name
+            ),
+            "Assign a Godot 4 engine.",
+        ));
+        return;
+    }
+
+    let Some(project_version) = project_file
+        .features()
+        .first()
+        .and_then(|feature| parse_major_minor(feature))
+    else {
+        return;
+    };
+
+    if project_version > engine_version {
+        diagnostics.push(Diagnostic::new(
+            Severity::Error,
+            format!(
+                "The project was made with Godot {}.{} but {} is older",
+                project_version.0, project_version.1, engine.version_name
+            ),
+            "Assign an engine of the same or a newer version.",
+        ));
+    } else if project_version < engine_version {
+        diagnostics.push(Diagnostic::new(
+            Severity::Info,
+            format!(
+                "The project was made with Godot {}.{} and will be upgraded when saved in {}",
+                project_version.0, project_version.1, engine.version_name
+            ),
+            "Back up the project before opening it, or assign a Godot engine of the same version.",
+        ));
+    }
+}
+
+/// Godot 3 caches imports in `.import/`, Godot 4 in `.godot/`. A cache from another engine version is rebuilt on open,
+/// which can take a long time for large projects.
+fn check_stale_cache(
+    project_directory: &Path,
+    engine: &GodotEngineVersion,
+    last_engine_name: Option<&str>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let Some(engine_version) = engine.major_minor() else {
+        return;
+    };
+
+    if engine_version.0 >= 4 && project_directory.join(".import").is_dir() {
+        diagnostics.push(Diagnostic::new(
+            Severity::Warning,
+            "The project has a .import folder left over from Godot 3".to_string(),
+            "Delete the .import folder, Godot 4 imports into .godot instead.",
+        ));
+    }
+
+    if engine_version.0 < 4 && project_directory.join(".godot").is_dir() {
+        diagnostics.push(Diagnostic::new(
+            Severity::Warning,
+            "The project has a .godot folder from Godot 4".to_string(),
+            "Delete the .godot folder, Godot 3 imports into .import instead.",
+        ));
+    }
+
+    let last_version =
+        last_engine_name.and_then(|name| parse_major_minor(&version_number_from_name(name)));
+
+    if let Some(last_version) = last_version {
+        if last_version != engine_version {
+            diagnostics.push(Diagnostic::new(
+                Severity::Info,
+                format!(
+                    "The import cache was built by Godot {}.{} but the project is assigned to {}",
+                    last_version.0, last_version.1, engine.version_name
+                ),
+                "Expect a full reimport on the next open, or clear the project's cache to start clean.",
+            ));
+        }
+    }
+}
+
+fn check_editor_plugins(
+    project_directory: &Path,
+    project_file: &ProjectFile,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for plugin in project_file.enabled_plugins() {
+        if !resolve_resource_path(project_directory, &plugin).is_some_and(|path| path.is_file()) {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                format!("The enabled editor plugin {} is missing", plugin),
+                "Reinstall the addon, or disable it in Project Settings > Plugins.",
+            ));
+        }
+    }
+}
+
+/// Reports import folders that are large enough to bloat a repository, but only when the project uses git
+/// and its `.gitignore` doesn't exclude them
+fn check_import_folders(project_directory: &Path, diagnostics: &mut Vec<Diagnostic>) {
+    if !project_directory.join(".git").exists() && !project_directory.join(".gitignore").exists() {
+        return;
+    }
+
+    let gitignore = fs::read_to_string(project_directory.join(".gitignore")).unwrap_or_default();
+
+    for folder in [".godot", ".import"] {
+        let ignored = gitignore.lines().any(|line| {
+            let line = line.trim().trim_start_matches('/').trim_end_matches('/');
+            line == folder || line == format!("{}/*", folder)
+        });
+        if ignored {
+            continue;
+        }
+
+        let size = directory_size(&project_directory.join(folder));
+        if size >= LARGE_IMPORT_FOLDER_BYTES {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                format!(
+                    "The {} folder is {} and not ignored by git",
+                    folder,
+                    format_size(size)
+                ),
+                "Add the folder to .gitignore, Godot regenerates it on import.",
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::godot_service::godot_engine_version::GodotEngineVersion;
+
+    use super::{diagnose_project, Severity};
+
+    fn engine(name: &str) -> GodotEngineVersion {
+        GodotEngineVersion::new(
+            name.to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_diagnose_godot_3_project_on_godot_4() {
+        let path = PathBuf::from("./test-data-diagnose-3to4");
+        fs::create_dir_all(path.join(".import")).unwrap();
+        fs::write(
+            path.join("project.godot"),
+            "config_version=4\n\n[application]\n\nconfig/name=\"Old Game\"\nrun/main_scene=\"res://main.tscn\"\n",
+        )
+        .unwrap();
+
+        let diagnostics = diagnose_project(&path, Some(&engine("Godot_v4.2.1-stable_win64")), None);
+
+        assert!(diagnostics.len() == 2);
+        assert!(diagnostics[0].severity == Severity::Error);
+        assert!(diagnostics[0].message.contains("Godot 3 project"));
+        assert!(diagnostics[1].message.contains(".import"));
+
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn test_diagnose_godot_4_project() {
+        let path = PathBuf::from("./test-data-diagnose-4");
+        fs::create_dir_all(path.join("addons").join("present")).unwrap();
+        fs::write(path.join("addons").join("present").join("plugin.cfg"), "").unwrap();
+        fs::write(
+            path.join("project.godot"),
+            r#"config_version=5
+
+[application]
+
+config/name="New Game"
+config/features=PackedStringArray("4.3", "Forward Plus")
+
+[editor_plugins]
+
+enabled=PackedStringArray("res://addons/present/plugin.cfg", "res://addons/missing/plugin.cfg")
+"#,
+        )
+        .unwrap();
+
+        let diagnostics = diagnose_project(
+            &path,
+            Some(&engine("Godot_v4.2.1-stable_win64")),
+            Some("Godot_v4.3-stable_win64"),
+        );
+        let messages: Vec<&str> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect();
+
+        assert!(diagnostics[0].severity == Severity::Error);
+        assert!(messages[0].contains("made with Godot 4.3"));
+        assert!(messages
+            .iter()
+            .any(|message| message.contains("res://addons/missing/plugin.cfg")));
+        assert!(!messages
+            .iter()
+            .any(|message| message.contains("res://addons/present/plugin.cfg")));
+        assert!(messages
+            .iter()
+            .any(|message| message.contains("main scene")));
+        assert!(messages
+            .iter()
+            .any(|message| message.contains("built by Godot 4.3")));
+
+        fs::remove_dir_all(path).unwrap();
+    }
+
+    #[test]
+    fn test_diagnose_missing_project_file() {
+        let diagnostics =
+            diagnose_project(&PathBuf::from("./test-data-diagnose-missing"), None, None);
+
+        assert!(diagnostics.len() == 1);
+        assert!(diagnostics[0].severity == Severity::Error);
+    }
+}
@@ -0,0 +1,230 @@
+use std::{collections::HashMap, fs, path::Path};
+
+/// A parsed `project.godot` file, or any other file in Godot's config format such as `export_presets.cfg`.
+/// Keys that appear before the first section header are stored under the empty section name `""`
+/// (this is where `config_version` lives).
+#[derive(Debug, Clone, Default)]
+pub struct ProjectFile {
+    sections: HashMap<String, HashMap<String, String>>,
+}
+
+impl ProjectFile {
+    /// Reads and parses the `project.godot` file at `path`. Returns `None` if the file cannot be read.
+    pub fn from_path(path: &Path) -> Option<ProjectFile> {
+        let contents = fs::read_to_string(path).ok()?;
+        Some(Self::parse(&contents))
+    }
+
+    /// Parses the contents of a `project.godot` file. Values spanning multiple lines (dictionaries and arrays)
+    /// are joined until their brackets are balanced.
+    pub fn parse(contents: &str) -> ProjectFile {
+        let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut current_section = "".to_string();
+        let mut pending: Option<(String, String)> = None;
+
+        for line in contents.lines() {
+            if let Some((key, mut value)) = pending.take() {
+                value.push('\n');
+                value.push_str(line);
+                if is_balanced(&value) {
+                    sections
+                        .entry(current_section.clone())
+                        .or_default()
+                        .insert(key, value);
+                } else {
+                    pending = Some((key, value));
+                }
+                continue;
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with(';') {
+                continue;
+            }
+
+            if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                current_section = trimmed[1..trimmed.len() - 1].to_string();
+                continue;
+            }
+
+            if let Some((key, value)) = trimmed.split_once('=') {
+                let key = key.trim().to_string();
+                let value = value.trim().to_string();
+                if is_balanced(&value) {
+                    sections
+                        .entry(current_section.clone())
+                        .or_default()
+                        .insert(key, value);
+                } else {
+                    pending = Some((key, value));
+                }
+            }
+        }
+
+        ProjectFile { sections }
+    }
+
+    /// Gets the raw value of `key` in `section`
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.sections
+            .get(section)
+            .and_then(|values| values.get(key))
+            .map(|value| value.as_str())
+    }
+
+    /// Gets the value of `key` in `section` with surrounding quotes removed
+    pub fn get_string(&self, section: &str, key: &str) -> Option<String> {
+        self.get(section, key).map(|value| unquote(value))
+    }
+
+    /// Gets every key in `section`
+    pub fn keys(&self, section: &str) -> Vec<String> {
+        self.sections
+            .get(section)
+            .map(|values| values.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Gets the name of every section, e.g. `application` or `preset.0.options`
+    pub fn sections(&self) -> Vec<String> {
+        self.sections.keys().cloned().collect()
+    }
+
+    pub fn has_section(&self, section: &str) -> bool {
+        self.sections.contains_key(section)
+    }
+
+    /// The `config_version` of the file. Godot 3 projects use 4, Godot 4 projects use 5.
+    pub fn config_version(&self) -> Option<u32> {
+        self.get("", "config_version")?.parse::<u32>().ok()
+    }
+
+    /// The `config/icon` of the project, e.g. `res://icon.svg`
+    pub fn icon(&self) -> Option<String> {
+        self.get_string("application", "config/icon")
+            .filter(|icon| !icon.is_empty())
+    }
+
+    /// The plugins enabled in `[editor_plugins]`, given as paths to their `plugin.cfg`
+    pub fn enabled_plugins(&self) -> Vec<String> {
+        match self.get("editor_plugins", "enabled") {
+            Some(value) => parse_string_array(value),
+            None => vec![],
+        }
+    }
+
+    /// The entries of `config/features`, e.g. `["4.2", "C#", "Forward Plus"]`
+    pub fn features(&self) -> Vec<String> {
+        match self.get("application", "config/features") {
+            Some(value) => parse_string_array(value),
+            None => vec![],
+        }
+    }
+}
+
+/// Parses a `PackedStringArray("a", "b")` or `["a", "b"]` value into its strings
+pub fn parse_string_array(value: &str) -> Vec<String> {
+    let start = value.find(|c| c == '(' || c == '[');
+    let end = value.rfind(|c| c == ')' || c == ']');
+
+    let inner = match (start, end) {
+        (Some(start), Some(end)) if start < end => &value[start + 1..end],
+        _ => return vec![],
+    };
+
+    inner
+        .split(',')
+        .map(|item| unquote(item.trim()))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches('"').to_string()
+}
+
+fn is_balanced(value: &str) -> bool {
+    let mut depth: i32 = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for c in value.chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    depth <= 0 && !in_string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_string_array, ProjectFile};
+
+    const PROJECT_FILE: &str = r#"; Engine configuration file.
+
+config_version=5
+
+[application]
+
+config/name="Test Project"
+config/features=PackedStringArray("4.2", "C#", "Forward Plus")
+config/icon="res://icon.svg"
+
+[autoload]
+
+Globals="*res://globals.gd"
+
+[input]
+
+jump={
+"deadzone": 0.5,
+"events": []
+}
+"#;
+
+    #[test]
+    fn test_parse_project_file() {
+        let project_file = ProjectFile::parse(PROJECT_FILE);
+
+        assert!(project_file.config_version() == Some(5));
+        assert!(
+            project_file.get_string("application", "config/name")
+                == Some("Test Project".to_string())
+        );
+        assert!(
+            project_file.get_string("application", "config/icon")
+                == Some("res://icon.svg".to_string())
+        );
+        assert!(project_file.features() == vec!["4.2", "C#", "Forward Plus"]);
+        assert!(project_file
+            .get("input", "jump")
+            .unwrap()
+            .contains("deadzone"));
+        assert!(project_file.has_section("autoload"));
+    }
+
+    #[test]
+    fn test_parse_string_array() {
+        assert!(parse_string_array(r#"PackedStringArray("a", "b")"#) == vec!["a", "b"]);
+        assert!(
+            parse_string_array(r#"["res://addons/a/plugin.cfg"]"#)
+                == vec!["res://addons/a/plugin.cfg"]
+        );
+        assert!(parse_string_array("PackedStringArray()").is_empty());
+    }
+}
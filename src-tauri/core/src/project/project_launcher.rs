@@ -0,0 +1,278 @@
+use std::{path::PathBuf, process::Command, sync::Arc};
+
+use crate::{
+    command::command::build_project_command,
+    directory::config_directory_service::{self, ConfigDirectoryService},
+    godot_service::godot_engine_version::GodotEngineVersion,
+    process::{
+        editor_lock::{self, EditorLockListener},
+        process_registry::ProcessListener,
+    },
+};
+
+use super::{
+    launch_profile::{self, LaunchMode, LaunchProfile},
+    project_data::ProjectData,
+    project_service,
+    usage_history::{self, UsageEvent, UsageHistoryListener},
+};
+
+/// A project that passed the checks for being opened, with the engine and launch profile it is opened with
+#[derive(Clone, Debug)]
+pub struct ProjectLaunch {
+    pub project: ProjectData,
+    pub engine: GodotEngineVersion,
+    pub profile: LaunchProfile,
+    pub project_directory: PathBuf,
+}
+
+impl ProjectLaunch {
+    pub fn is_editor(&self) -> bool {
+        self.profile.mode == LaunchMode::Editor
+    }
+
+    /// Builds the command that opens the project
+    pub fn command(&self) -> Command {
+        build_project_command(&self.project, &self.engine, &self.profile)
+    }
+
+    /// Wraps `inner` in the listeners that remove the editor lock and close the session in the usage history
+    /// once the editor exits
+    pub fn editor_listener(
+        &self,
+        config_directory: &ConfigDirectoryService,
+        inner: Arc<dyn ProcessListener>,
+    ) -> Arc<dyn ProcessListener> {
+        Arc::new(EditorLockListener {
+            project_directory: self.project_directory.clone(),
+            inner: Arc::new(UsageHistoryListener {
+                history_path: config_directory.history_path(),
+                project_name: self.project.project_name.clone(),
+                inner,
+            }),
+        })
+    }
+}
+
+/// Finds the project `project_name` with its engine and resolves the launch profile `profile_name`.
+/// Opening is refused with the compatibility warnings of the project and engine, and opening the editor while
+/// another editor has the project open, unless `force` is set.
+/// `running_editor` is the editor the caller itself started for the project, if any.
+pub fn prepare_launch(
+    projects: &[ProjectData],
+    installed_versions: &[GodotEngineVersion],
+    project_name: &str,
+    profile_name: Option<&str>,
+    running_editor: Option<u32>,
+    force: bool,
+) -> Result<ProjectLaunch, String> {
+    let project = projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .ok_or(format!("Did not find a project with name {}", project_name))?;
+
+    let engine = installed_versions
+        .iter()
+        .find(|engine| engine.version_name == project.engine_version)
+        .ok_or(format!(
+            "Did not find a godot engine with name {}",
+            project.engine_version
+        ))?;
+
+    let launch = ProjectLaunch {
+        profile: launch_profile::resolve_launch_profile(project, profile_name)?,
+        project_directory: project_service::project_directory(&project.project_path),
+        project: project.clone(),
+        engine: engine.clone(),
+    };
+
+    if launch.is_editor() && !force {
+        editor_lock::ensure_not_open(project_name, &launch.project_directory, running_editor)?;
+    }
+
+    if !force {
+        let warnings = project_service::project_compatibility_warnings(project, engine);
+        if !warnings.is_empty() {
+            return Err(warnings.join("\n"));
+        }
+    }
+
+    Ok(launch)
+}
+
+/// Records that `launch` was started as the process `id` at `started_at`. An editor locks the project, and opens
+/// a session in the usage history if `watches_exit` is set, meaning the caller closes the session with
+/// [`ProjectLaunch::editor_listener`]. The project's last opened date is set and saved to the config.
+/// The process is already running at this point, so failing to record it is only logged.
+pub fn record_launch(
+    launch: &ProjectLaunch,
+    id: u32,
+    started_at: i64,
+    watches_exit: bool,
+    projects: &mut Vec<ProjectData>,
+    config_directory: &ConfigDirectoryService,
+) {
+    if launch.is_editor() {
+        editor_lock::write_editor_lock(&launch.project_directory, id)
+            .map_err(|error| println!("Could not write editor lock: {}", error))
+            .ok();
+    }
+
+    if launch.is_editor() && watches_exit {
+        usage_history::append_usage_event(
+            &config_directory.history_path(),
+            &launch.project.project_name,
+            &UsageEvent::Opened {
+                id,
+                timestamp: started_at,
+                engine_name: launch.engine.version_name.clone(),
+            },
+        )
+        .map_err(|error| println!("Could not write usage history: {}", error))
+        .ok();
+    }
+
+    if let Some(project) = projects
+        .iter_mut()
+        .find(|project| project.project_name == launch.project.project_name)
+    {
+        project.last_date_opened = started_at;
+    }
+
+    config_directory_service::save_projects_to_config(config_directory, projects);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use chrono::Local;
+
+    use crate::{
+        directory::config_directory_service::ConfigDirectoryService,
+        godot_service::godot_engine_version::GodotEngineVersion,
+        process::editor_lock,
+        project::{project_data::ProjectData, usage_history},
+    };
+
+    use super::{prepare_launch, record_launch};
+
+    #[test]
+    fn test_prepare_and_record_launch() {
+        let config_directory = ConfigDirectoryService::new_test(
+            "./test-data-launcher".to_string(),
+            "config.json".to_string(),
+        );
+        fs::create_dir_all("./test-data-launcher/my_game").unwrap();
+        fs::write("./test-data-launcher/my_game/project.godot", "").unwrap();
+
+        let engines = vec![GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        )];
+        let mut projects = vec![ProjectData::new(
+            "./test-data-launcher/my_game/project.godot".to_string(),
+            "Godot_v4.2.1-stable_win64".to_string(),
+            0,
+            true,
+            true,
+        )];
+
+        assert!(prepare_launch(&projects, &[], "my_game", None, None, false).is_err());
+        assert!(prepare_launch(&projects, &engines, "other_game", None, None, false).is_err());
+        assert!(
+            prepare_launch(&projects, &engines, "my_game", Some("missing"), None, false).is_err()
+        );
+
+        let launch = prepare_launch(&projects, &engines, "my_game", None, None, false).unwrap();
+        assert!(launch.is_editor());
+
+        let id = std::process::id();
+        let now = Local::now().timestamp_millis();
+        record_launch(&launch, id, now, false, &mut projects, &config_directory);
+        assert!(
+            usage_history::read_usage_sessions(&config_directory.history_path(), "my_game")
+                .is_empty()
+        );
+
+        record_launch(&launch, id, now, true, &mut projects, &config_directory);
+
+        assert!(projects[0].last_date_opened == now);
+        assert!(editor_lock::locked_editor_id(&launch.project_directory) == Some(id));
+        assert!(
+            usage_history::read_usage_sessions(&config_directory.history_path(), "my_game").len()
+                == 1
+        );
+
+        // The editor recorded above still has the project open
+        assert!(prepare_launch(&projects, &engines, "my_game", None, None, false).is_err());
+        assert!(prepare_launch(&projects, &engines, "my_game", None, None, true).is_ok());
+
+        editor_lock::remove_editor_lock(&launch.project_directory);
+        assert!(prepare_launch(&projects, &engines, "my_game", None, Some(id), false).is_err());
+
+        fs::remove_dir_all("./test-data-launcher").unwrap();
+    }
+    #[test]
+    fn test_prepare_launch_compatibility_warnings() {
+        let engines = vec![GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        )];
+        let mut project = ProjectData::new(
+            "./test-data-launcher-csharp/my_game/project.godot".to_string(),
+            "Godot_v4.2.1-stable_win64".to_string(),
+            0,
+            true,
+            true,
+        );
+        project.is_csharp = true;
+        let projects = vec![project];
+
+        let error = prepare_launch(&projects, &engines, "my_game", None, None, false).unwrap_err();
+        assert!(error.contains("is not a .NET engine"));
+        assert!(prepare_launch(&projects, &engines, "my_game", None, None, true).is_ok());
+    }
+    #[test]
+    fn test_prepare_launch_godot_3_project() {
+        fs::create_dir_all("./test-data-launcher-godot3/old_game").unwrap();
+        fs::write(
+            "./test-data-launcher-godot3/old_game/project.godot",
+            "config_version=4\n",
+        )
+        .unwrap();
+
+        let engine = |name: &str| {
+            GodotEngineVersion::new(
+                name.to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+            )
+        };
+        let engines = vec![
+            engine("Godot_v3.5.3-stable_win64"),
+            engine("Godot_v4.2.1-stable_win64"),
+        ];
+        let mut projects = vec![ProjectData::new(
+            "./test-data-launcher-godot3/old_game/project.godot".to_string(),
+            "Godot_v3.5.3-stable_win64".to_string(),
+            0,
+            true,
+            true,
+        )];
+
+        assert!(prepare_launch(&projects, &engines, "old_game", None, None, false).is_ok());
+
+        projects[0].engine_version = "Godot_v4.2.1-stable_win64".to_string();
+        let error = prepare_launch(&projects, &engines, "old_game", None, None, false).unwrap_err();
+        assert!(error.contains("is a Godot 3 project"));
+        assert!(prepare_launch(&projects, &engines, "old_game", None, None, true).is_ok());
+
+        fs::remove_dir_all("./test-data-launcher-godot3").unwrap();
+    }
+}
@@ -0,0 +1,239 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use super::project_data::ProjectData;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub enum ProjectSortKey {
+    Name,
+    #[default]
+    LastOpened,
+    EngineVersion,
+    /// Sorts by the alphabetically first tag of each project, untagged projects last
+    Tag,
+    Favorite,
+}
+
+/// How the project list is filtered and sorted. Favorites are always listed before other projects.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ProjectQuery {
+    /// Only keeps projects whose name contains this text, ignoring case
+    #[serde(default)]
+    pub search: Option<String>,
+    #[serde(
+        rename(serialize = "engineVersion", deserialize = "engineVersion"),
+        default
+    )]
+    pub engine_version: Option<String>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    #[serde(
+        rename(serialize = "favoritesOnly", deserialize = "favoritesOnly"),
+        default
+    )]
+    pub favorites_only: bool,
+    #[serde(rename(serialize = "sortBy", deserialize = "sortBy"), default)]
+    pub sort_by: ProjectSortKey,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+/// Filters and sorts `projects` according to `query`
+pub fn query_projects(projects: &Vec<ProjectData>, query: &ProjectQuery) -> Vec<ProjectData> {
+    let search = query.search.as_ref().map(|search| search.to_lowercase());
+
+    let mut matching: Vec<ProjectData> = projects
+        .iter()
+        .filter(|project| {
+            search
+                .as_ref()
+                .is_none_or(|search| project.project_name.to_lowercase().contains(search))
+        })
+        .filter(|project| {
+            query
+                .engine_version
+                .as_ref()
+                .is_none_or(|engine_version| &project.engine_version == engine_version)
+        })
+        .filter(|project| {
+            query
+                .tag
+                .as_ref()
+                .is_none_or(|tag| project.metadata.tags.contains(tag))
+        })
+        .filter(|project| !query.favorites_only || project.metadata.favorite)
+        .cloned()
+        .collect();
+
+    matching.sort_by(|a, b| {
+        let ordering = compare_projects(a, b, &query.sort_by);
+        let ordering = if query.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+
+        b.metadata.favorite.cmp(&a.metadata.favorite).then(ordering)
+    });
+
+    matching
+}
+
+fn compare_projects(a: &ProjectData, b: &ProjectData, sort_by: &ProjectSortKey) -> Ordering {
+    let by_name = a
+        .project_name
+        .to_lowercase()
+        .cmp(&b.project_name.to_lowercase());
+
+    match sort_by {
+        ProjectSortKey::Name | ProjectSortKey::Favorite => by_name,
+        ProjectSortKey::LastOpened => b.last_date_opened.cmp(&a.last_date_opened).then(by_name),
+        ProjectSortKey::EngineVersion => version_sort_key(&a.engine_version)
+            .cmp(&version_sort_key(&b.engine_version))
+            .then(by_name),
+        ProjectSortKey::Tag => match (first_tag(a), first_tag(b)) {
+            (Some(a_tag), Some(b_tag)) => a_tag.cmp(&b_tag).then(by_name),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => by_name,
+        },
+    }
+}
+
+fn first_tag(project: &ProjectData) -> Option<String> {
+    project
+        .metadata
+        .tags
+        .iter()
+        .map(|tag| tag.to_lowercase())
+        .min()
+}
+
+/// Gets the version numbers of an engine name like `Godot_v4.2.1-stable_win64` so versions sort numerically
+fn version_sort_key(engine_version: &str) -> Vec<u32> {
+    let Some((_, version)) = engine_version.split_once("_v") else {
+        return vec![];
+    };
+
+    version
+        .split('-')
+        .next()
+        .unwrap_or("")
+        .split('.')
+        .filter_map(|part| part.parse::<u32>().ok())
+        .collect()
+}
+
+/// Gets every tag used by `projects`, sorted and without duplicates
+pub fn all_tags(projects: &Vec<ProjectData>) -> Vec<String> {
+    let mut tags: Vec<String> = projects
+        .iter()
+        .flat_map(|project| project.metadata.tags.clone())
+        .collect();
+
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::project::project_data::ProjectData;
+
+    use super::{all_tags, query_projects, ProjectQuery, ProjectSortKey};
+
+    fn project(name: &str, engine_version: &str, last_opened: i64, tags: Vec<&str>) -> ProjectData {
+        let mut project = ProjectData::new(
+            format!("./projects/{}/project.godot", name),
+            engine_version.to_string(),
+            last_opened,
+            true,
+            true,
+        );
+        project.metadata.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        project
+    }
+
+    fn names(projects: Vec<ProjectData>) -> Vec<String> {
+        projects
+            .into_iter()
+            .map(|project| project.project_name)
+            .collect()
+    }
+
+    #[test]
+    fn test_query_projects() {
+        let mut projects = vec![
+            project("platformer", "Godot_v4.10.0-stable_win64", 10, vec!["jam"]),
+            project("Racer", "Godot_v4.2.1-stable_win64", 30, vec![]),
+            project(
+                "shooter",
+                "Godot_v3.5.3-stable_win64",
+                20,
+                vec!["prototype", "3d"],
+            ),
+        ];
+
+        let by_last_opened = query_projects(&projects, &ProjectQuery::default());
+        assert!(names(by_last_opened) == vec!["Racer", "shooter", "platformer"]);
+
+        let by_engine = query_projects(
+            &projects,
+            &ProjectQuery {
+                sort_by: ProjectSortKey::EngineVersion,
+                descending: true,
+                ..Default::default()
+            },
+        );
+        assert!(names(by_engine) == vec!["platformer", "Racer", "shooter"]);
+
+        let by_tag = query_projects(
+            &projects,
+            &ProjectQuery {
+                sort_by: ProjectSortKey::Tag,
+                ..Default::default()
+            },
+        );
+        assert!(names(by_tag) == vec!["shooter", "platformer", "Racer"]);
+
+        projects[2].metadata.favorite = true;
+        let favorites_first = query_projects(
+            &projects,
+            &ProjectQuery {
+                sort_by: ProjectSortKey::Name,
+                ..Default::default()
+            },
+        );
+        assert!(names(favorites_first) == vec!["shooter", "platformer", "Racer"]);
+
+        let filtered = query_projects(
+            &projects,
+            &ProjectQuery {
+                search: Some("ER".to_string()),
+                tag: Some("prototype".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(names(filtered) == vec!["shooter"]);
+
+        let favorites = query_projects(
+            &projects,
+            &ProjectQuery {
+                favorites_only: true,
+                ..Default::default()
+            },
+        );
+        assert!(names(favorites) == vec!["shooter"]);
+    }
+
+    #[test]
+    fn test_all_tags() {
+        let projects = vec![
+            project("platformer", "", 0, vec!["jam", "2d"]),
+            project("shooter", "", 0, vec!["jam", "3d"]),
+        ];
+
+        assert!(all_tags(&projects) == vec!["2d", "3d", "jam"]);
+    }
+}
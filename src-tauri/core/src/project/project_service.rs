@@ -0,0 +1,661 @@
+use crate::{
+    directory::config_directory_service::{self, ConfigDirectoryService},
+    godot_service::{
+        dotnet_service::{self, DotnetStatus},
+        godot_engine_version::GodotEngineVersion,
+    },
+};
+
+use super::{project_data::ProjectData, project_file::ProjectFile};
+use directories::BaseDirs;
+use serde_json::to_string;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub struct ProjectDirectoryService {
+    base_path: String,
+}
+
+impl ProjectDirectoryService {
+    pub fn new(base_path: &str) -> ProjectDirectoryService {
+        ProjectDirectoryService {
+            base_path: base_path.to_string(),
+        }
+    }
+
+    pub fn find_projects(&self) -> Vec<ProjectData> {
+        let projects = Self::scan_path(PathBuf::from(&self.base_path));
+
+        projects
+    }
+
+    fn scan_path(path: PathBuf) -> Vec<ProjectData> {
+        let mut project_paths: Vec<ProjectData> = vec![];
+        let mut nested_paths: Vec<PathBuf> = vec![];
+
+        let paths = fs::read_dir(path).unwrap();
+
+        for path in paths {
+            let p = path.unwrap().path();
+            if p.display().to_string().contains("project.godot") {
+                let project = ProjectData::new(
+                    p.to_str().unwrap().to_string(),
+                    "".to_string(),
+                    -1,
+                    false,
+                    false,
+                );
+                project_paths.push(project);
+                return project_paths;
+            }
+
+            if p.display().to_string() == ".." {
+                continue;
+            }
+
+            if p.is_dir() {
+                nested_paths.push(p);
+            }
+        }
+
+        for path in nested_paths {
+            let mut result = Self::scan_path(path.to_owned());
+            project_paths.append(&mut result)
+        }
+
+        project_paths
+    }
+}
+
+/// Scans every tracked directory in the config for projects
+pub fn get_all_projects_from_dirs(config_directory: &ConfigDirectoryService) -> Vec<ProjectData> {
+    let config = config_directory_service::get_project_config(&config_directory);
+    let mut all_projects: Vec<ProjectData> = vec![];
+
+    for directory in config.tracked_directories {
+        let project_service = ProjectDirectoryService::new(&directory);
+        let mut projects = project_service.find_projects();
+        all_projects.append(&mut projects);
+    }
+
+    all_projects
+}
+
+/// Takes in the existing_projects (which are cached in the config, with possibly an associated engine and other data),
+/// the found_projects (which are newly scanned projects from the directories to be tracked), and all_godot_versions (which are all versions downloaded),
+/// and reconciles them. This will combine the existing projects and found projects while validating their valid_path and valid_engine properties.
+pub fn project_reconciliation(
+    existing_projects: Vec<ProjectData>,
+    found_projects: Vec<ProjectData>,
+    all_godot_versions: &Vec<GodotEngineVersion>,
+) -> Vec<ProjectData> {
+    let existing_project_set: HashMap<String, ProjectData> = existing_projects
+        .into_iter()
+        .map(|x| (x.project_name.clone(), x))
+        .collect();
+
+    // for project in found_projects {
+    //     projectSet.insert(project.project_name, project);
+    // }
+
+    let mut reconciled_projects = found_projects;
+
+    for project in &mut reconciled_projects {
+        if existing_project_set.contains_key(&project.project_name) {
+            project.engine_version = existing_project_set[&project.project_name]
+                .engine_version
+                .clone();
+
+            project.last_date_opened = existing_project_set[&project.project_name]
+                .last_date_opened
+                .clone();
+
+            project.launch_profiles = existing_project_set[&project.project_name]
+                .launch_profiles
+                .clone();
+
+            project.default_launch_profile = existing_project_set[&project.project_name]
+                .default_launch_profile
+                .clone();
+
+            project.metadata = existing_project_set[&project.project_name].metadata.clone();
+        }
+    }
+
+    validate_project_paths(&mut reconciled_projects);
+    validate_godot_versions(&mut reconciled_projects, &all_godot_versions);
+    detect_csharp_projects(&mut reconciled_projects);
+
+    reconciled_projects
+}
+
+/// Checks each ProjectData object and sets path_valid based on if the path it holds is valid or not
+pub fn validate_project_paths(projects: &mut Vec<ProjectData>) {
+    for project in projects {
+        project.path_valid = PathBuf::from(&project.project_path).exists();
+    }
+}
+
+/// Checks each ProjectData object and sets engine_valid based on if there is a matching engine version and the path to the engine version is valid
+pub fn validate_godot_versions(
+    projects: &mut Vec<ProjectData>,
+    godot_versions: &Vec<GodotEngineVersion>,
+) {
+    for project in projects {
+        let godot_version = godot_versions
+            .iter()
+            .find(|godot| godot.version_name == project.engine_version);
+        project.engine_valid =
+            godot_version.is_some() && PathBuf::from(&godot_version.unwrap().path).exists();
+    }
+}
+
+/// Checks each ProjectData object and sets is_csharp based on if the project uses C#
+pub fn detect_csharp_projects(projects: &mut Vec<ProjectData>) {
+    for project in projects {
+        project.is_csharp = is_csharp_project(&project_directory(&project.project_path));
+    }
+}
+
+/// Gets the folder of a project from the path to its `project.godot` file
+pub fn project_directory(project_path: &str) -> PathBuf {
+    let path = PathBuf::from(project_path);
+    match path.parent() {
+        Some(parent) if path.ends_with("project.godot") => parent.to_path_buf(),
+        _ => path,
+    }
+}
+
+/// A project is a C# project if it has a `.csproj` or `.sln` file in its folder,
+/// or if `project.godot` lists `C#` in its `config/features`
+pub fn is_csharp_project(project_directory: &Path) -> bool {
+    if let Ok(entries) = fs::read_dir(project_directory) {
+        let has_solution = entries.filter_map(|entry| entry.ok()).any(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "csproj" || extension == "sln")
+        });
+
+        if has_solution {
+            return true;
+        }
+    }
+
+    ProjectFile::from_path(&project_directory.join("project.godot")).is_some_and(|project_file| {
+        project_file
+            .features()
+            .iter()
+            .any(|feature| feature == "C#")
+    })
+}
+
+/// Gets the warnings to show before opening `project` with `engine`
+pub fn engine_compatibility_warnings(
+    project: &ProjectData,
+    engine: &GodotEngineVersion,
+    dotnet_status: &DotnetStatus,
+) -> Vec<String> {
+    let mut warnings: Vec<String> = vec![];
+
+    if project.is_csharp && !engine.is_mono {
+        warnings.push(format!(
+            "{} is a C# project but {} is not a .NET engine. C# scripts will not run.",
+            project.project_name, engine.version_name
+        ));
+    }
+
+    if project.is_csharp && engine.is_mono && !dotnet_status.satisfied {
+        if let Some(required) = dotnet_status.required_major_version {
+            warnings.push(format!(
+                "{} requires the .NET {} SDK or newer, but it was not found.",
+                engine.version_name, required
+            ));
+        }
+    }
+
+    if is_godot_3_project(&project_directory(&project.project_path))
+        && engine.major_minor().is_some_and(|(major, _)| major >= 4)
+    {
+        warnings.push(format!(
+            "{} is a Godot 3 project. Opening it in {} will convert it in place, use the upgrade assistant to back it up and convert it first.",
+            project.project_name, engine.version_name
+        ));
+    }
+
+    warnings
+}
+
+/// Gets the warnings to show before opening `project` with `engine`, looking up the installed .NET SDKs
+/// only for a C# project opened with a .NET engine
+pub fn project_compatibility_warnings(
+    project: &ProjectData,
+    engine: &GodotEngineVersion,
+) -> Vec<String> {
+    let installed_sdks = if project.is_csharp && engine.is_mono {
+        dotnet_service::get_installed_dotnet_sdks()
+    } else {
+        vec![]
+    };
+
+    engine_compatibility_warnings(
+        project,
+        engine,
+        &dotnet_service::check_dotnet_for_engine(engine, installed_sdks),
+    )
+}
+
+/// Assigns the engine `replacement` to the projects that use the engine `engine_name`, or marks their engine
+/// as invalid if there is no replacement. Returns the names of the changed projects.
+pub fn reassign_engine(
+    projects: &mut [ProjectData],
+    engine_name: &str,
+    replacement: Option<&str>,
+) -> Vec<String> {
+    projects
+        .iter_mut()
+        .filter(|project| project.engine_version == engine_name)
+        .map(|project| {
+            match replacement {
+                Some(replacement) => {
+                    project.engine_version = replacement.to_string();
+                    project.engine_valid = true;
+                }
+                None => {
+                    project.engine_version = "".to_string();
+                    project.engine_valid = false;
+                }
+            }
+            project.project_name.clone()
+        })
+        .collect()
+}
+
+/// Moves the projects that use the engine `engine_name` to its upgrade `latest_name`, except projects that
+/// pinned their engine. Returns the names of the changed projects.
+pub fn reassign_upgraded_engine(
+    projects: &mut [ProjectData],
+    engine_name: &str,
+    latest_name: &str,
+) -> Vec<String> {
+    projects
+        .iter_mut()
+        .filter(|project| project.engine_version == engine_name && !project.metadata.pin_engine)
+        .map(|project| {
+            project.engine_version = latest_name.to_string();
+            project.engine_valid = true;
+            project.project_name.clone()
+        })
+        .collect()
+}
+
+/// A project is a Godot 3 project if its `config_version` is below 5
+pub fn is_godot_3_project(project_directory: &Path) -> bool {
+    ProjectFile::from_path(&project_directory.join("project.godot"))
+        .and_then(|project_file| project_file.config_version())
+        .is_some_and(|config_version| config_version < 5)
+}
+
+/// Finds every scene (`.tscn` or `.scn`) in a project folder, returned as sorted `res://` paths.
+/// Hidden folders such as `.godot` are skipped.
+pub fn find_project_scenes(project_directory: &Path) -> Vec<String> {
+    let mut scenes: Vec<String> = vec![];
+    let mut pending: Vec<PathBuf> = vec![project_directory.to_path_buf()];
+
+    while let Some(directory) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&directory) else {
+            continue;
+        };
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+
+            if path.is_dir() {
+                pending.push(path);
+            } else if path
+                .extension()
+                .is_some_and(|extension| extension == "tscn" || extension == "scn")
+            {
+                let relative = path.strip_prefix(project_directory).unwrap();
+                let components: Vec<String> = relative
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy().to_string())
+                    .collect();
+                scenes.push(format!("res://{}", components.join("/")));
+            }
+        }
+    }
+
+    scenes.sort();
+    scenes
+}
+
+pub fn open_project(project_path: PathBuf) {
+    if !project_path.exists() {
+        return;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Local;
+    use directories::BaseDirs;
+    use std::{
+        fs::{self, File},
+        path::PathBuf,
+    };
+
+    use crate::{
+        directory::config_directory_service::{self, ConfigDirectoryService},
+        godot_service::dotnet_service::DotnetStatus,
+        godot_service::godot_engine_version::GodotEngineVersion,
+        godot_service::release_channels::ReleaseFilter,
+        news::news_cache::NewsSettings,
+        project::{
+            project_backup::BackupSettings,
+            project_data::{ProjectConfig, ProjectData},
+            project_service::{
+                engine_compatibility_warnings, find_project_scenes, get_all_projects_from_dirs,
+                is_csharp_project, project_reconciliation, reassign_engine,
+                reassign_upgraded_engine, ProjectDirectoryService,
+            },
+        },
+    };
+
+    const TEST_PATH: &str = "./test_data/projects/";
+
+    fn setup() {
+        let mut path1 = PathBuf::from(TEST_PATH);
+        path1.push("project1");
+        fs::create_dir_all(&path1).unwrap();
+        path1.push("project.godot");
+
+        let mut path2 = PathBuf::from(TEST_PATH);
+        path2.push("project2");
+        fs::create_dir_all(&path2).unwrap();
+        path2.push("godot2.nope");
+
+        let mut path3 = PathBuf::from(TEST_PATH);
+        path3.push("project3");
+        fs::create_dir_all(&path3).unwrap();
+        path3.push("project.godot");
+
+        File::create(path1).unwrap();
+        File::create(path2).unwrap();
+        File::create(path3).unwrap();
+    }
+
+    fn cleanup() {
+        fs::remove_dir_all("./test_data").unwrap();
+    }
+
+    #[test]
+    fn test_find_projects() {
+        setup();
+        let path = TEST_PATH;
+        println!("searching {}", &path);
+        let project_directory = ProjectDirectoryService::new(path);
+
+        let projects = project_directory.find_projects();
+        println!("Found {} projects", projects.len());
+        for project in &projects {
+            println!("{} - {}", project.project_name, project.project_path)
+        }
+        assert!(projects.len() == 2);
+        cleanup();
+    }
+
+    #[test]
+    fn test_project_dir() {
+        let dir = BaseDirs::new().unwrap();
+        println!("path: {}", dir.config_dir().display());
+        // assert!(dir)
+    }
+
+    #[test]
+    fn test_project_reconciliation() {
+        let mut existing = vec![ProjectData::new(
+            "test".to_string(),
+            "V1".to_string(),
+            Local::now().timestamp(),
+            true,
+            false,
+        )];
+        existing[0].metadata.favorite = true;
+        existing[0].metadata.tags = vec!["jam".to_string()];
+
+        let found = vec![
+            ProjectData::new(
+                "test".to_string(),
+                "".to_string(),
+                Local::now().timestamp(),
+                true,
+                false,
+            ),
+            ProjectData::new(
+                "test2".to_string(),
+                "".to_string(),
+                Local::now().timestamp(),
+                true,
+                false,
+            ),
+        ];
+
+        let godot_projects = vec![GodotEngineVersion::new(
+            "godot 1".to_string(),
+            "1".to_string(),
+            "".to_string(),
+            "".to_string(),
+        )];
+
+        let reconciled = project_reconciliation(existing, found, &godot_projects);
+
+        assert!(reconciled.len() == 2);
+        assert!(reconciled.first().unwrap().engine_version == "V1");
+        assert!(reconciled.first().unwrap().path_valid == false);
+        assert!(reconciled.first().unwrap().engine_valid == false);
+        assert!(reconciled.first().unwrap().metadata.favorite);
+        assert!(reconciled.first().unwrap().metadata.tags == vec!["jam"]);
+        assert!(!reconciled.last().unwrap().metadata.favorite);
+    }
+
+    #[test]
+    fn test_reassign_engine() {
+        let project = |path: &str, engine: &str| {
+            ProjectData::new(
+                path.to_string(),
+                engine.to_string(),
+                Local::now().timestamp(),
+                true,
+                true,
+            )
+        };
+        let mut projects = vec![
+            project("./old_game/project.godot", "Godot_v4.1.3-stable_win64"),
+            project("./new_game/project.godot", "Godot_v4.2.1-stable_win64"),
+        ];
+
+        let changed = reassign_engine(
+            &mut projects,
+            "Godot_v4.1.3-stable_win64",
+            Some("Godot_v4.2.1-stable_win64"),
+        );
+
+        assert!(changed == vec!["old_game"]);
+        assert!(projects[0].engine_version == "Godot_v4.2.1-stable_win64");
+        assert!(projects[0].engine_valid);
+
+        let changed = reassign_engine(&mut projects, "Godot_v4.2.1-stable_win64", None);
+
+        assert!(changed.len() == 2);
+        assert!(projects.iter().all(|project| !project.engine_valid));
+        assert!(projects
+            .iter()
+            .all(|project| project.engine_version.is_empty()));
+    }
+
+    #[test]
+    fn test_reassign_upgraded_engine() {
+        let project = |path: &str| {
+            ProjectData::new(
+                path.to_string(),
+                "Godot_v4.2.1-stable_win64".to_string(),
+                Local::now().timestamp(),
+                true,
+                true,
+            )
+        };
+        let mut projects = vec![
+            project("./my_game/project.godot"),
+            project("./pinned_game/project.godot"),
+        ];
+        projects[1].metadata.pin_engine = true;
+
+        let changed = reassign_upgraded_engine(
+            &mut projects,
+            "Godot_v4.2.1-stable_win64",
+            "Godot_v4.2.2-stable_win64",
+        );
+
+        assert!(changed == vec!["my_game"]);
+        assert!(projects[0].engine_version == "Godot_v4.2.2-stable_win64");
+        assert!(projects[1].engine_version == "Godot_v4.2.1-stable_win64");
+    }
+
+    #[test]
+    fn test_is_csharp_project() {
+        let path = PathBuf::from("./test_data_csharp/projects/");
+        fs::create_dir_all(path.join("solution")).unwrap();
+        fs::create_dir_all(path.join("features")).unwrap();
+        fs::create_dir_all(path.join("gdscript")).unwrap();
+
+        File::create(path.join("solution").join("Game.csproj")).unwrap();
+        fs::write(
+            path.join("features").join("project.godot"),
+            "[application]\nconfig/features=PackedStringArray(\"4.2\", \"C#\")\n",
+        )
+        .unwrap();
+        fs::write(
+            path.join("gdscript").join("project.godot"),
+            "[application]\nconfig/features=PackedStringArray(\"4.2\")\n",
+        )
+        .unwrap();
+
+        assert!(is_csharp_project(&path.join("solution")));
+        assert!(is_csharp_project(&path.join("features")));
+        assert!(!is_csharp_project(&path.join("gdscript")));
+
+        fs::remove_dir_all("./test_data_csharp").unwrap();
+    }
+
+    #[test]
+    fn test_find_project_scenes() {
+        let path = PathBuf::from("./test_data_scenes/project/");
+        fs::create_dir_all(path.join("levels")).unwrap();
+        fs::create_dir_all(path.join(".godot").join("editor")).unwrap();
+
+        File::create(path.join("project.godot")).unwrap();
+        File::create(path.join("main.tscn")).unwrap();
+        File::create(path.join("levels").join("level_1.scn")).unwrap();
+        File::create(path.join("levels").join("player.gd")).unwrap();
+        File::create(path.join(".godot").join("editor").join("cached.tscn")).unwrap();
+
+        let scenes = find_project_scenes(&path);
+
+        assert!(scenes == vec!["res://levels/level_1.scn", "res://main.tscn"]);
+
+        fs::remove_dir_all("./test_data_scenes").unwrap();
+    }
+
+    #[test]
+    fn test_engine_compatibility_warnings() {
+        let mut project = ProjectData::new(
+            "test\\project.godot".to_string(),
+            "".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        );
+        project.is_csharp = true;
+
+        let engine = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_win64".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        );
+        let mono_engine = GodotEngineVersion::new(
+            "Godot_v4.2.1-stable_mono_win64".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        );
+        let missing_sdk = DotnetStatus {
+            required_major_version: Some(6),
+            installed_sdks: vec![],
+            satisfied: false,
+        };
+
+        assert!(engine_compatibility_warnings(&project, &engine, &missing_sdk).len() == 1);
+        assert!(engine_compatibility_warnings(&project, &mono_engine, &missing_sdk).len() == 1);
+
+        project.is_csharp = false;
+        assert!(engine_compatibility_warnings(&project, &engine, &missing_sdk).is_empty());
+
+        fs::create_dir_all("./test-data-godot3/old_game").unwrap();
+        fs::write(
+            "./test-data-godot3/old_game/project.godot",
+            "config_version=4\n",
+        )
+        .unwrap();
+        let godot_3_project = ProjectData::new(
+            "./test-data-godot3/old_game/project.godot".to_string(),
+            "".to_string(),
+            Local::now().timestamp(),
+            true,
+            true,
+        );
+
+        let warnings = engine_compatibility_warnings(&godot_3_project, &engine, &missing_sdk);
+        assert!(warnings.len() == 1);
+        assert!(warnings[0].contains("Godot 3 project"));
+
+        fs::remove_dir_all("./test-data-godot3").unwrap();
+    }
+
+    #[test]
+    fn test_get_all_projects_from_dirs() {
+        fs::create_dir_all("./test-project/projects/project1".to_string()).unwrap();
+        fs::File::create("./test-project/projects/project1/project.godot").unwrap();
+
+        let config = &ProjectConfig {
+            tracked_directories: vec!["./test-project/projects".to_string()],
+            tracked_godot_versions: vec![],
+            tracked_projects: vec![],
+            backup_settings: BackupSettings::default(),
+            release_filter: ReleaseFilter::default(),
+            news_settings: NewsSettings::default(),
+        };
+
+        let config_directory = ConfigDirectoryService::new_test(
+            "./test-project-config".to_string(),
+            "config.json".to_string(),
+        );
+
+        config_directory_service::save_project_config(&config_directory, config);
+
+        let all_projects = get_all_projects_from_dirs(&config_directory);
+
+        assert!(all_projects.len() == 1);
+
+        fs::remove_dir_all("./test-project").unwrap();
+        fs::remove_dir_all("./test-project-config").unwrap();
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::project_file::ProjectFile;
+
+/// Cached artwork of a project. The paths point into the thumbnail cache and can be loaded through the asset protocol.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProjectThumbnail {
+    #[serde(rename(serialize = "iconPath", deserialize = "iconPath"))]
+    pub icon_path: Option<String>,
+    #[serde(rename(serialize = "screenshotPath", deserialize = "screenshotPath"))]
+    pub screenshot_path: Option<String>,
+}
+
+/// Resolves a `res://` path to a file in the project folder. Other paths are returned relative to the project folder.
+/// Returns `None` for paths that would leave the project folder, e.g. `res://../../secret.png`.
+pub fn resolve_resource_path(project_directory: &Path, resource_path: &str) -> Option<PathBuf> {
+    let relative = resource_path
+        .strip_prefix("res://")
+        .unwrap_or(resource_path);
+
+    relative
+        .split(['/', '\\'])
+        .filter(|part| !part.is_empty() && *part != ".")
+        .try_fold(project_directory.to_path_buf(), |path, part| {
+            let mut components = Path::new(part).components();
+            match (components.next(), components.next()) {
+                (Some(Component::Normal(_)), None) => Some(path.join(part)),
+                _ => None,
+            }
+        })
+}
+
+/// Gets the icon file set in `config/icon` of the project, if it exists
+pub fn find_project_icon(project_directory: &Path) -> Option<PathBuf> {
+    let icon = ProjectFile::from_path(&project_directory.join("project.godot"))?.icon()?;
+    let path = resolve_resource_path(project_directory, &icon)?;
+    path.is_file().then_some(path)
+}
+
+/// Gets the newest image the editor saved in the project's `.godot/editor` folder, which exists once the project was opened
+pub fn find_editor_screenshot(project_directory: &Path) -> Option<PathBuf> {
+    fs::read_dir(project_directory.join(".godot").join("editor"))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "png" || extension == "jpg")
+        })
+        .max_by_key(|path| modified_time(path))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Copies `source` into the thumbnail cache, named after a hash of the project folder so projects with
+/// the same name don't collide. The copy is only refreshed when the source changed since it was cached.
+fn cache_image(
+    cache_path: &Path,
+    project_directory: &Path,
+    kind: &str,
+    source: &Path,
+) -> std::io::Result<PathBuf> {
+    let hash = Sha256::digest(project_directory.to_string_lossy().as_bytes());
+    let extension = source
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or("png".to_string());
+    let target = cache_path.join(format!("{:x}_{}.{}", hash, kind, extension));
+
+    if modified_time(&target) < modified_time(source) {
+        fs::create_dir_all(cache_path)?;
+        fs::copy(source, &target)?;
+    }
+
+    Ok(target)
+}
+
+/// Gets the icon and editor screenshot of a project, copying them into `cache_path` if they changed
+pub fn get_project_thumbnail(cache_path: &Path, project_directory: &Path) -> ProjectThumbnail {
+    let cache = |kind: &str, source: Option<PathBuf>| {
+        source
+            .and_then(|source| {
+                cache_image(cache_path, project_directory, kind, &source)
+                    .map_err(|error| println!("Could not cache {:?}: {}", source, error))
+                    .ok()
+            })
+            .map(|path| path.to_string_lossy().to_string())
+    };
+
+    ProjectThumbnail {
+        icon_path: cache("icon", find_project_icon(project_directory)),
+        screenshot_path: cache("screenshot", find_editor_screenshot(project_directory)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{get_project_thumbnail, resolve_resource_path};
+
+    #[test]
+    fn test_resolve_resource_path() {
+        let project_directory = PathBuf::from("./my_game");
+
+        assert!(
+            resolve_resource_path(&project_directory, "res://art/icon.png")
+                == Some(project_directory.join("art").join("icon.png"))
+        );
+        assert!(
+            resolve_resource_path(&project_directory, "icon.svg")
+                == Some(project_directory.join("icon.svg"))
+        );
+        assert!(resolve_resource_path(&project_directory, "res://../../secret.png").is_none());
+        assert!(
+            resolve_resource_path(&project_directory, "res://art/..\\..\\secret.png").is_none()
+        );
+        assert!(
+            resolve_resource_path(&project_directory, "/etc/passwd")
+                == Some(project_directory.join("etc").join("passwd"))
+        );
+    }
+
+    #[test]
+    fn test_get_project_thumbnail() {
+        let path = PathBuf::from("./test-data-thumbnail");
+        let project_directory = path.join("my_game");
+        let cache_path = path.join("thumbnails");
+
+        fs::create_dir_all(project_directory.join("art")).unwrap();
+        fs::write(
+            project_directory.join("project.godot"),
+            "config_version=5\n\n[application]\n\nconfig/icon=\"res://art/icon.svg\"\n",
+        )
+        .unwrap();
+        fs::write(project_directory.join("art").join("icon.svg"), "<svg/>").unwrap();
+
+        let thumbnail = get_project_thumbnail(&cache_path, &project_directory);
+        let icon_path = PathBuf::from(thumbnail.icon_path.unwrap());
+
+        assert!(icon_path.starts_with(&cache_path));
+        assert!(icon_path.extension().unwrap() == "svg");
+        assert!(fs::read_to_string(icon_path).unwrap() == "<svg/>");
+        assert!(thumbnail.screenshot_path.is_none());
+
+        fs::create_dir_all(project_directory.join(".godot").join("editor")).unwrap();
+        fs::write(
+            project_directory
+                .join(".godot")
+                .join("editor")
+                .join("screenshot.png"),
+            "png",
+        )
+        .unwrap();
+
+        let thumbnail = get_project_thumbnail(&cache_path, &project_directory);
+        assert!(thumbnail.screenshot_path.is_some());
+
+        fs::remove_dir_all(path).unwrap();
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    command::command::build_convert_command,
+    godot_service::godot_engine_version::GodotEngineVersion,
+};
+
+use super::{
+    project_backup::{create_backup, BackupSettings},
+    project_data::ProjectData,
+    project_service::{is_godot_3_project, project_directory},
+};
+
+/// The result of converting a Godot 3 project to Godot 4
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UpgradeReport {
+    #[serde(rename(serialize = "dryRun", deserialize = "dryRun"))]
+    pub dry_run: bool,
+    pub success: bool,
+    /// The lines Godot printed, which list the changes that were or would be made
+    pub output: Vec<String>,
+    /// The backup made before converting, none for a dry run
+    #[serde(rename(serialize = "backupPath", deserialize = "backupPath"))]
+    pub backup_path: Option<String>,
+}
+
+/// Converts the Godot 3 project `project` with the Godot 4 `engine` and waits for it to finish.
+/// A dry run only reports the changes. Otherwise the project is backed up to `backups_path` with `backup_settings` first,
+/// and not converted if the backup fails. Assigning `engine` to the project afterwards is up to the caller.
+pub fn upgrade_project(
+    project: &ProjectData,
+    engine: &GodotEngineVersion,
+    backups_path: &Path,
+    backup_settings: &BackupSettings,
+    dry_run: bool,
+) -> Result<UpgradeReport, String> {
+    let directory = project_directory(&project.project_path);
+
+    if !is_godot_3_project(&directory) {
+        return Err(format!("{} is not a Godot 3 project", project.project_name));
+    }
+
+    if engine.major_minor().is_none_or(|(major, _)| major < 4) {
+        return Err(format!(
+            "{} cannot convert projects, select a Godot 4 engine",
+            engine.version_name
+        ));
+    }
+
+    let backup_path = if dry_run {
+        None
+    } else {
+        let backup = create_backup(project, backups_path, backup_settings)
+            .map_err(|error| format!("Could not back up {}: {}", project.project_name, error))?;
+        Some(backup.archive_path)
+    };
+
+    let output = build_convert_command(project, engine, dry_run)
+        .output()
+        .map_err(|error| format!("Could not run {}: {}", engine.version_name, error))?;
+
+    Ok(UpgradeReport {
+        dry_run,
+        success: output.status.success(),
+        output: String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .chain(String::from_utf8_lossy(&output.stderr).lines())
+            .map(|line| line.to_string())
+            .collect(),
+        backup_path,
+    })
+}
@@ -0,0 +1,271 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::process::{
+    process_log::sanitize_name,
+    process_registry::{ProcessInfo, ProcessListener, ProcessOutput},
+};
+
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+const HOUR_MILLIS: f64 = 60.0 * 60.0 * 1000.0;
+
+/// A line in a project's usage history. Each editor launch writes an `Opened` event, and a `Closed` event
+/// with the same process id once the editor exits.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "event")]
+pub enum UsageEvent {
+    Opened {
+        id: u32,
+        timestamp: i64,
+        #[serde(rename(serialize = "engineName", deserialize = "engineName"))]
+        engine_name: String,
+    },
+    Closed {
+        id: u32,
+        timestamp: i64,
+        #[serde(rename(serialize = "exitCode", deserialize = "exitCode"))]
+        exit_code: Option<i32>,
+        crashed: bool,
+    },
+}
+
+/// One editor session of a project. The end is unknown if the manager was closed before the editor.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct UsageSession {
+    #[serde(rename(serialize = "openedAt", deserialize = "openedAt"))]
+    pub opened_at: i64,
+    #[serde(rename(serialize = "closedAt", deserialize = "closedAt"))]
+    pub closed_at: Option<i64>,
+    #[serde(rename(serialize = "engineName", deserialize = "engineName"))]
+    pub engine_name: String,
+    #[serde(rename(serialize = "exitCode", deserialize = "exitCode"))]
+    pub exit_code: Option<i32>,
+    pub crashed: bool,
+}
+
+impl UsageSession {
+    pub fn duration_millis(&self) -> i64 {
+        self.closed_at
+            .map(|closed_at| (closed_at - self.opened_at).max(0))
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ProjectStats {
+    #[serde(rename(serialize = "timesOpened", deserialize = "timesOpened"))]
+    pub times_opened: usize,
+    #[serde(rename(serialize = "totalEditorHours", deserialize = "totalEditorHours"))]
+    pub total_editor_hours: f64,
+    #[serde(rename(
+        serialize = "timesOpenedLast30Days",
+        deserialize = "timesOpenedLast30Days"
+    ))]
+    pub times_opened_last_30_days: usize,
+    #[serde(rename(
+        serialize = "editorHoursLast30Days",
+        deserialize = "editorHoursLast30Days"
+    ))]
+    pub editor_hours_last_30_days: f64,
+    pub crashes: usize,
+    #[serde(rename(serialize = "lastOpened", deserialize = "lastOpened"))]
+    pub last_opened: Option<i64>,
+    pub sessions: Vec<UsageSession>,
+}
+
+/// Gets the history file of a project, one JSON encoded `UsageEvent` per line
+pub fn usage_history_file(history_path: &Path, project_name: &str) -> PathBuf {
+    history_path.join(format!("{}.jsonl", sanitize_name(project_name)))
+}
+
+/// Appends `event` to the history of a project. Existing lines are never rewritten.
+pub fn append_usage_event(
+    history_path: &Path,
+    project_name: &str,
+    event: &UsageEvent,
+) -> std::io::Result<()> {
+    fs::create_dir_all(history_path)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(usage_history_file(history_path, project_name))?;
+
+    writeln!(file, "{}", serde_json::to_string(event).unwrap())
+}
+
+/// Reads the history of a project and pairs each `Opened` event with its `Closed` event.
+/// Lines that cannot be parsed, e.g. one that was cut off by a crash, are skipped.
+pub fn read_usage_sessions(history_path: &Path, project_name: &str) -> Vec<UsageSession> {
+    let contents =
+        fs::read_to_string(usage_history_file(history_path, project_name)).unwrap_or_default();
+    let mut sessions: Vec<(u32, UsageSession)> = vec![];
+
+    for event in contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<UsageEvent>(line).ok())
+    {
+        match event {
+            UsageEvent::Opened {
+                id,
+                timestamp,
+                engine_name,
+            } => sessions.push((
+                id,
+                UsageSession {
+                    opened_at: timestamp,
+                    closed_at: None,
+                    engine_name,
+                    exit_code: None,
+                    crashed: false,
+                },
+            )),
+            UsageEvent::Closed {
+                id,
+                timestamp,
+                exit_code,
+                crashed,
+            } => {
+                if let Some((_, session)) = sessions
+                    .iter_mut()
+                    .rev()
+                    .find(|(session_id, session)| *session_id == id && session.closed_at.is_none())
+                {
+                    session.closed_at = Some(timestamp);
+                    session.exit_code = exit_code;
+                    session.crashed = crashed;
+                }
+            }
+        }
+    }
+
+    sessions.into_iter().map(|(_, session)| session).collect()
+}
+
+/// Aggregates `sessions` into stats, counting the last 30 days from `now` (in milliseconds)
+pub fn project_stats(sessions: Vec<UsageSession>, now: i64) -> ProjectStats {
+    let recent: Vec<&UsageSession> = sessions
+        .iter()
+        .filter(|session| session.opened_at >= now - 30 * DAY_MILLIS)
+        .collect();
+
+    ProjectStats {
+        times_opened: sessions.len(),
+        total_editor_hours: sessions
+            .iter()
+            .map(|session| session.duration_millis())
+            .sum::<i64>() as f64
+            / HOUR_MILLIS,
+        times_opened_last_30_days: recent.len(),
+        editor_hours_last_30_days: recent
+            .iter()
+            .map(|session| session.duration_millis())
+            .sum::<i64>() as f64
+            / HOUR_MILLIS,
+        crashes: sessions.iter().filter(|session| session.crashed).count(),
+        last_opened: sessions.iter().map(|session| session.opened_at).max(),
+        sessions,
+    }
+}
+
+/// Records the exit of an editor in the usage history of its project, then passes the event on to `inner`
+pub struct UsageHistoryListener {
+    pub history_path: PathBuf,
+    pub project_name: String,
+    pub inner: Arc<dyn ProcessListener>,
+}
+
+impl ProcessListener for UsageHistoryListener {
+    fn output(&self, output: &ProcessOutput) {
+        self.inner.output(output);
+    }
+
+    fn exited(&self, process: &ProcessInfo) {
+        append_usage_event(
+            &self.history_path,
+            &self.project_name,
+            &UsageEvent::Closed {
+                id: process.id,
+                timestamp: process.exited_at.unwrap_or(Local::now().timestamp_millis()),
+                exit_code: process.exit_code,
+                crashed: process.crashed,
+            },
+        )
+        .map_err(|error| println!("Could not write usage history: {}", error))
+        .ok();
+
+        self.inner.exited(process);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{append_usage_event, project_stats, read_usage_sessions, UsageEvent, DAY_MILLIS};
+
+    #[test]
+    fn test_usage_history() {
+        let path = PathBuf::from("./test-data-history");
+        let now = 100 * DAY_MILLIS;
+        let hour = 60 * 60 * 1000;
+
+        let events = vec![
+            UsageEvent::Opened {
+                id: 1,
+                timestamp: now - 40 * DAY_MILLIS,
+                engine_name: "Godot_v4.1.3-stable_win64".to_string(),
+            },
+            UsageEvent::Closed {
+                id: 1,
+                timestamp: now - 40 * DAY_MILLIS + 2 * hour,
+                exit_code: Some(0),
+                crashed: false,
+            },
+            UsageEvent::Opened {
+                id: 2,
+                timestamp: now - DAY_MILLIS,
+                engine_name: "Godot_v4.2.1-stable_win64".to_string(),
+            },
+            UsageEvent::Closed {
+                id: 2,
+                timestamp: now - DAY_MILLIS + hour,
+                exit_code: Some(139),
+                crashed: true,
+            },
+            UsageEvent::Opened {
+                id: 3,
+                timestamp: now - hour,
+                engine_name: "Godot_v4.2.1-stable_win64".to_string(),
+            },
+        ];
+
+        for event in &events {
+            append_usage_event(&path, "my_game", event).unwrap();
+        }
+
+        let sessions = read_usage_sessions(&path, "my_game");
+        assert!(sessions.len() == 3);
+        assert!(sessions[1].crashed);
+        assert!(sessions[2].closed_at.is_none());
+
+        let stats = project_stats(sessions, now);
+        assert!(stats.times_opened == 3);
+        assert!(stats.total_editor_hours == 3.0);
+        assert!(stats.times_opened_last_30_days == 2);
+        assert!(stats.editor_hours_last_30_days == 1.0);
+        assert!(stats.crashes == 1);
+        assert!(stats.last_opened == Some(now - hour));
+
+        assert!(read_usage_sessions(&path, "other_game").is_empty());
+
+        fs::remove_dir_all(path).unwrap();
+    }
+}
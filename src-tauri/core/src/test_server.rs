@@ -0,0 +1,77 @@
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+/// Starts a minimal HTTP server on a random local port that answers GET requests with the body registered
+/// for the request path, or 404 if there is none. Returns the base url of the server, e.g. `http://127.0.0.1:1234`.
+/// The server runs until the test's runtime shuts down.
+pub async fn serve(routes: Vec<(&str, Vec<u8>)>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let routes: Arc<HashMap<String, Vec<u8>>> = Arc::new(
+        routes
+            .into_iter()
+            .map(|(path, body)| (path.to_string(), body))
+            .collect(),
+    );
+
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                return;
+            };
+            let routes = routes.clone();
+
+            tokio::spawn(async move {
+                let mut request: Vec<u8> = vec![];
+                let mut buffer = [0u8; 1024];
+
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(read) => request.extend_from_slice(&buffer[..read]),
+                    }
+                }
+
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+                let (status, body) = match routes.get(path) {
+                    Some(body) => ("200 OK", body.clone()),
+                    None => ("404 Not Found", vec![]),
+                };
+
+                let header = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+
+                stream.write_all(header.as_bytes()).await.ok();
+                stream.write_all(&body).await.ok();
+                stream.shutdown().await.ok();
+            });
+        }
+    });
+
+    format!("http://{}", address)
+}
+
+/// Builds an in-memory zip archive containing `files`, given as `(path, contents)` pairs
+pub fn build_zip(files: Vec<(&str, &[u8])>) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut archive = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::FileOptions::default();
+
+    for (path, contents) in files {
+        archive.start_file(path, options).unwrap();
+        archive.write_all(contents).unwrap();
+    }
+
+    archive.finish().unwrap().into_inner()
+}
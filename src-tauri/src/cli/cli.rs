@@ -0,0 +1,972 @@
+use std::{fs, io::Write};
+
+use chrono::{Local, TimeZone};
+use serde::Serialize;
+
+use godot_manager_core::{
+    command::{self, command::ProjectTask},
+    directory::config_directory_service::{self, ConfigDirectoryService},
+    fetcher::{download_service, progress_reporter::ProgressReporter, release_notes},
+    godot_service::{
+        engine_updates, godot_engine_service,
+        godot_engine_version::{GodotEngineVersion, ReleaseChannel},
+        release_channels::{self, ReleaseFlavor},
+    },
+    process::editor_lock,
+    project::{
+        export_preset,
+        project_data::ProjectData,
+        project_diagnostics, project_launcher,
+        project_query::{self, ProjectQuery, ProjectSortKey},
+        project_service, project_upgrade, usage_history,
+    },
+};
+
+use super::table::format_table;
+
+const SUBCOMMANDS: [&str; 6] = ["engines", "projects", "dirs", "help", "--help", "-h"];
+
+const USAGE: &str = "Usage: godot_manager <command> [--json]
+
+Commands:
+  engines list                          List installed engines
+  engines available [--all]             List engines that can be installed in the visible channels
+  engines channels [<channel>...]       Show or set the visible channels: stable, rc, beta, alpha, dev
+  engines latest [--mono]               Show the newest stable engine
+  engines install <engine> [--self-contained]
+                                        Download and install an engine
+  engines remove <engine> [--force] [--reassign=<engine>]
+                                        Remove an installed engine, --force if projects use it
+  engines notes <engine>                Show the release notes of an engine version, also offline
+  engines updates                       List newer releases of the installed engines
+  engines upgrade <engine> [--reassign] Install the update of an engine, --reassign moves its unpinned projects
+  projects list [--sort=<name|opened|engine|tag>] [--desc] [--tag=<tag>] [--favorites]
+                                        List tracked projects, favorites first
+  projects scan                         Scan tracked directories for projects
+  projects open <project> [--profile=<name>] [--force]
+                                        Open a project with its assigned engine
+  projects set-engine <project> <engine>
+                                        Assign an installed engine to a project
+  projects diagnose <project>           Check a project for problems
+  projects upgrade <project> <engine> [--dry-run]
+                                        Back up a Godot 3 project and convert it for a Godot 4 engine
+  projects presets <project>            List the export presets of a project
+  projects export <project> <preset> [--output=<path>] [--debug]
+                                        Export a project headlessly
+  projects import <project>             Import the resources of a project headlessly
+  projects script <project> <script>    Run a script on a project headlessly
+  dirs list                             List tracked project directories
+  dirs add <path>                       Track a project directory
+  dirs remove <path>                    Stop tracking a project directory
+
+Options:
+  --json                                Print results as JSON instead of a table";
+
+/// Arguments passed on the command line, split into positional arguments and `--flags`
+pub struct CliArgs {
+    pub positional: Vec<String>,
+    pub flags: Vec<String>,
+}
+
+impl CliArgs {
+    pub fn parse(args: &[String]) -> CliArgs {
+        let (flags, positional): (Vec<String>, Vec<String>) =
+            args.iter().cloned().partition(|arg| arg.starts_with("--"));
+
+        CliArgs { positional, flags }
+    }
+
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|value| value == flag)
+    }
+
+    /// Gets the value of a `--flag=value` argument
+    pub fn flag_value(&self, flag: &str) -> Option<&str> {
+        self.flags.iter().find_map(|value| {
+            value
+                .strip_prefix(flag)
+                .and_then(|value| value.strip_prefix('='))
+        })
+    }
+
+    fn get(&self, index: usize) -> Result<&str, String> {
+        self.positional
+            .get(index)
+            .map(|value| value.as_str())
+            .ok_or(USAGE.to_string())
+    }
+}
+
+/// Checks if the manager was started from a terminal with a subcommand, in which case no window should be opened
+pub fn is_cli_invocation(args: &[String]) -> bool {
+    args.first()
+        .is_some_and(|arg| SUBCOMMANDS.contains(&arg.as_str()))
+}
+
+/// Runs the subcommand in `args` and returns the exit code of the process
+pub async fn run(args: Vec<String>) -> i32 {
+    let args = CliArgs::parse(&args);
+
+    match run_command(&args).await {
+        Ok(output) => {
+            print!("{}", output);
+            0
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            1
+        }
+    }
+}
+
+async fn run_command(args: &CliArgs) -> Result<String, String> {
+    let json = args.has_flag("--json");
+
+    match (
+        args.get(0)?,
+        args.positional.get(1).map(|value| value.as_str()),
+    ) {
+        ("engines", Some("list")) => list_engines(json),
+        ("engines", Some("available")) => {
+            list_available_engines(args.has_flag("--all"), json).await
+        }
+        ("engines", Some("channels")) => visible_channels(&args.positional[2..], json),
+        ("engines", Some("latest")) => latest_stable_engine(args.has_flag("--mono"), json).await,
+        ("engines", Some("install")) => {
+            install_engine(args.get(2)?, args.has_flag("--self-contained"), json).await
+        }
+        ("engines", Some("remove")) => remove_engine(
+            args.get(2)?,
+            args.has_flag("--force"),
+            args.flag_value("--reassign"),
+        ),
+        ("engines", Some("notes")) => show_release_notes(args.get(2)?, json).await,
+        ("engines", Some("updates")) => list_engine_updates(json).await,
+        ("engines", Some("upgrade")) => {
+            upgrade_engine(args.get(2)?, args.has_flag("--reassign"), json).await
+        }
+        ("projects", Some("list")) => list_projects(&project_query_from_args(args)?, json),
+        ("projects", Some("scan")) => scan_projects(json),
+        ("projects", Some("open")) => open_project(
+            args.get(2)?,
+            args.flag_value("--profile"),
+            args.has_flag("--force"),
+        ),
+        ("projects", Some("set-engine")) => set_project_engine(args.get(2)?, args.get(3)?, json),
+        ("projects", Some("diagnose")) => diagnose_project(args.get(2)?, json),
+        ("projects", Some("upgrade")) => {
+            upgrade_project(args.get(2)?, args.get(3)?, args.has_flag("--dry-run"), json)
+        }
+        ("projects", Some("presets")) => list_export_presets(args.get(2)?, json),
+        ("projects", Some("export")) => run_project_task(
+            args.get(2)?,
+            ProjectTask::Export {
+                preset: args.get(3)?.to_string(),
+                output_path: args.flag_value("--output").map(|path| path.to_string()),
+                debug: args.has_flag("--debug"),
+            },
+        ),
+        ("projects", Some("import")) => run_project_task(args.get(2)?, ProjectTask::Import),
+        ("projects", Some("script")) => run_project_task(
+            args.get(2)?,
+            ProjectTask::Script {
+                path: args.get(3)?.to_string(),
+            },
+        ),
+        ("dirs", Some("list")) => list_directories(json),
+        ("dirs", Some("add")) => add_directory(args.get(2)?, json),
+        ("dirs", Some("remove")) => remove_directory(args.get(2)?, json),
+        ("help" | "--help" | "-h", _) => Ok(format!("{}\n", USAGE)),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, String> {
+    serde_json::to_string_pretty(value)
+        .map(|json| format!("{}\n", json))
+        .map_err(|error| error.to_string())
+}
+
+fn installed_engines(
+    directory_service: &ConfigDirectoryService,
+) -> Result<Vec<GodotEngineVersion>, String> {
+    let engine_storage_path = directory_service.engine_storage_path();
+    fs::create_dir_all(engine_storage_path).map_err(|error| {
+        format!(
+            "Could not create the engines folder {}: {}",
+            engine_storage_path.display(),
+            error
+        )
+    })?;
+    Ok(godot_engine_service::get_installed_godot_versions(
+        directory_service,
+    ))
+}
+
+fn format_engines(engines: &Vec<GodotEngineVersion>, json: bool) -> Result<String, String> {
+    if json {
+        return to_json(engines);
+    }
+
+    let rows = engines
+        .iter()
+        .map(|engine| {
+            vec![
+                engine.version_name.clone(),
+                engine.version_number.clone(),
+                yes_no(engine.is_mono),
+                yes_no(engine.self_contained),
+                yes_no(engine.export_templates_installed),
+                engine.path.clone(),
+            ]
+        })
+        .collect();
+
+    Ok(format_table(
+        &[
+            "NAME",
+            "VERSION",
+            ".NET",
+            "SELF-CONTAINED",
+            "TEMPLATES",
+            "PATH",
+        ],
+        &rows,
+    ))
+}
+
+fn format_projects(projects: &Vec<ProjectData>, json: bool) -> Result<String, String> {
+    if json {
+        return to_json(projects);
+    }
+
+    let rows = projects
+        .iter()
+        .map(|project| {
+            vec![
+                format!(
+                    "{}{}",
+                    if project.metadata.favorite { "* " } else { "" },
+                    project.project_name
+                ),
+                project.engine_version.clone(),
+                format_timestamp(project.last_date_opened),
+                project.metadata.tags.join(", "),
+                yes_no(project.path_valid && project.engine_valid),
+                project.project_path.clone(),
+            ]
+        })
+        .collect();
+
+    Ok(format_table(
+        &["NAME", "ENGINE", "LAST OPENED", "TAGS", "VALID", "PATH"],
+        &rows,
+    ))
+}
+
+fn format_directories(directories: &Vec<String>, json: bool) -> Result<String, String> {
+    if json {
+        return to_json(directories);
+    }
+
+    let rows = directories
+        .iter()
+        .map(|directory| vec![directory.clone()])
+        .collect();
+
+    Ok(format_table(&["DIRECTORY"], &rows))
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+/// Formats a `last_date_opened` value, which is in milliseconds and -1 for projects that were never opened
+fn format_timestamp(timestamp_millis: i64) -> String {
+    match Local.timestamp_millis_opt(timestamp_millis) {
+        chrono::LocalResult::Single(time) if timestamp_millis > 0 => {
+            time.format("%Y-%m-%d %H:%M").to_string()
+        }
+        _ => "never".to_string(),
+    }
+}
+
+fn list_engines(json: bool) -> Result<String, String> {
+    let directory_service = ConfigDirectoryService::new();
+    format_engines(&installed_engines(&directory_service)?, json)
+}
+
+async fn list_available_engines(all: bool, json: bool) -> Result<String, String> {
+    let directory_service = ConfigDirectoryService::new();
+    let mut catalog =
+        download_service::get_engine_catalog(&reqwest::Client::new(), &directory_service)
+            .await
+            .map_err(|error| format!("Could not fetch available engines: {}", error))?;
+
+    if !all {
+        let release_filter =
+            config_directory_service::get_project_config(&directory_service).release_filter;
+        catalog = release_channels::filter_catalog(&catalog, &release_filter);
+    }
+
+    if json {
+        return to_json(&catalog);
+    }
+
+    let rows = catalog
+        .iter()
+        .map(|engine| {
+            vec![
+                engine.version_name.clone(),
+                engine.version_number.clone(),
+                engine
+                    .release_version()
+                    .map(|version| version.channel.name().to_string())
+                    .unwrap_or_default(),
+                yes_no(engine.is_mono),
+                engine.updated_at.clone(),
+            ]
+        })
+        .collect();
+
+    Ok(format_table(
+        &["NAME", "VERSION", "CHANNEL", ".NET", "UPDATED"],
+        &rows,
+    ))
+}
+
+/// Prints the visible release channels, after replacing them with `channels` if any are given
+fn visible_channels(channels: &[String], json: bool) -> Result<String, String> {
+    let directory_service = ConfigDirectoryService::new();
+    let mut release_filter =
+        config_directory_service::get_project_config(&directory_service).release_filter;
+
+    if !channels.is_empty() {
+        release_filter.channels = channels
+            .iter()
+            .map(|channel| {
+                ReleaseChannel::from_name(channel)
+                    .ok_or(format!("{} is not a release channel", channel))
+            })
+            .collect::<Result<Vec<ReleaseChannel>, String>>()?;
+        config_directory_service::save_release_filter_to_config(
+            &directory_service,
+            &release_filter,
+        );
+    }
+
+    if json {
+        return to_json(&release_filter.channels);
+    }
+
+    let rows = release_filter
+        .channels
+        .iter()
+        .map(|channel| vec![channel.name().to_string()])
+        .collect();
+
+    Ok(format_table(&["CHANNEL"], &rows))
+}
+
+async fn latest_stable_engine(mono: bool, json: bool) -> Result<String, String> {
+    let directory_service = ConfigDirectoryService::new();
+    let catalog = download_service::get_engine_catalog(&reqwest::Client::new(), &directory_service)
+        .await
+        .map_err(|error| format!("Could not fetch available engines: {}", error))?;
+
+    let flavor = if mono {
+        ReleaseFlavor::Mono
+    } else {
+        ReleaseFlavor::Standard
+    };
+
+    let latest = release_channels::latest_stable(&catalog, flavor)
+        .ok_or("Did not find a stable engine".to_string())?;
+
+    if json {
+        return to_json(latest);
+    }
+
+    Ok(format!("{}\n", latest.version_name))
+}
+
+async fn install_engine(
+    engine_name: &str,
+    self_contained: bool,
+    json: bool,
+) -> Result<String, String> {
+    let directory_service = ConfigDirectoryService::new();
+
+    if installed_engines(&directory_service)?
+        .iter()
+        .any(|engine| engine.version_name == engine_name)
+    {
+        return Err(format!("{} is already installed", engine_name));
+    }
+
+    let client = reqwest::Client::new();
+    let catalog = download_service::get_engine_catalog(&client, &directory_service)
+        .await
+        .map_err(|error| format!("Could not fetch available engines: {}", error))?;
+
+    let engine = catalog
+        .into_iter()
+        .find(|engine| engine.version_name == engine_name)
+        .ok_or(format!(
+            "Did not find a godot engine with name {}",
+            engine_name
+        ))?;
+
+    let installed = download_service::download_and_extract_engine(
+        &client,
+        &directory_service,
+        &engine,
+        self_contained,
+        &ConsoleProgress,
+    )
+    .await
+    .map_err(|error| format!("Could not install {}: {}", engine_name, error))?;
+
+    format_engines(&vec![installed], json)
+}
+
+/// Prints download progress to stderr so it doesn't mix with `--json` output
+struct ConsoleProgress;
+
+impl ProgressReporter for ConsoleProgress {
+    fn report(&self, name: &str, progress: usize) {
+        eprint!("\rDownloading {}: {}%", name, progress);
+        std::io::stderr().flush().ok();
+    }
+
+    fn finish(&self, name: &str) {
+        eprintln!("\rDownloaded {}      ", name);
+    }
+}
+
+fn remove_engine(
+    engine_name: &str,
+    force: bool,
+    reassign_to: Option<&str>,
+) -> Result<String, String> {
+    let directory_service = ConfigDirectoryService::new();
+    let engines = installed_engines(&directory_service)?;
+    let mut config = config_directory_service::get_project_config(&directory_service);
+
+    let removal = godot_engine_service::remove_engine(
+        engine_name,
+        &engines,
+        &mut config.tracked_projects,
+        force,
+        reassign_to,
+        &directory_service,
+    )?;
+    let dependents = removal.dependents;
+
+    if !removal.removed {
+        return Err(format!(
+            "{} is used by {}. Use --force to remove it anyway, and --reassign=<engine> to move the projects to another engine",
+            engine_name,
+            dependents.join(", ")
+        ));
+    }
+
+    Ok(match (dependents.is_empty(), reassign_to) {
+        (true, _) => format!("Removed {}\n", engine_name),
+        (false, Some(replacement)) => format!(
+            "Removed {} and assigned {} to {}\n",
+            engine_name,
+            replacement,
+            dependents.join(", ")
+        ),
+        (false, None) => format!(
+            "Removed {}, {} no longer have a valid engine\n",
+            engine_name,
+            dependents.join(", ")
+        ),
+    })
+}
+
+async fn show_release_notes(version: &str, json: bool) -> Result<String, String> {
+    let directory_service = ConfigDirectoryService::new();
+    let notes =
+        release_notes::get_release_notes(&reqwest::Client::new(), &directory_service, version)
+            .await?;
+
+    if json {
+        return to_json(&notes);
+    }
+
+    Ok(format!(
+        "{} ({})\n{}\n\n{}\n",
+        notes.tag_name,
+        notes.published_at.unwrap_or_default(),
+        notes.html_url,
+        notes.body.replace("\r\n", "\n")
+    ))
+}
+
+async fn list_engine_updates(json: bool) -> Result<String, String> {
+    let directory_service = ConfigDirectoryService::new();
+    let catalog = download_service::get_engine_catalog(&reqwest::Client::new(), &directory_service)
+        .await
+        .map_err(|error| format!("Could not fetch available engines: {}", error))?;
+
+    let updates =
+        engine_updates::find_engine_updates(&installed_engines(&directory_service)?, &catalog);
+
+    if json {
+        return to_json(&updates);
+    }
+
+    let rows = updates
+        .iter()
+        .map(|update| {
+            vec![
+                update.engine_name.clone(),
+                update.latest_name.clone(),
+                update.latest_version.clone(),
+            ]
+        })
+        .collect();
+
+    Ok(format_table(&["ENGINE", "UPDATE", "VERSION"], &rows))
+}
+
+async fn upgrade_engine(
+    engine_name: &str,
+    reassign_projects: bool,
+    json: bool,
+) -> Result<String, String> {
+    let directory_service = ConfigDirectoryService::new();
+    let engines = installed_engines(&directory_service)?;
+
+    let client = reqwest::Client::new();
+    let catalog = download_service::get_engine_catalog(&client, &directory_service)
+        .await
+        .map_err(|error| format!("Could not fetch available engines: {}", error))?;
+
+    let installed = engine_updates::upgrade_engine(
+        &client,
+        &directory_service,
+        engine_name,
+        &engines,
+        &catalog,
+        &ConsoleProgress,
+    )
+    .await?;
+
+    if reassign_projects {
+        let mut config = config_directory_service::get_project_config(&directory_service);
+        let reassigned = engine_updates::reassign_to_upgrade(
+            &mut config.tracked_projects,
+            engine_name,
+            &installed,
+            &directory_service,
+        );
+
+        if !json && !reassigned.is_empty() {
+            eprintln!(
+                "Assigned {} to {}",
+                installed.version_name,
+                reassigned.join(", ")
+            );
+        }
+    }
+
+    format_engines(&vec![installed], json)
+}
+
+fn project_query_from_args(args: &CliArgs) -> Result<ProjectQuery, String> {
+    let sort_by = match args.flag_value("--sort") {
+        None | Some("opened") => ProjectSortKey::LastOpened,
+        Some("name") => ProjectSortKey::Name,
+        Some("engine") => ProjectSortKey::EngineVersion,
+        Some("tag") => ProjectSortKey::Tag,
+        Some(other) => return Err(format!("Unknown sort order {}\n\n{}", other, USAGE)),
+    };
+
+    Ok(ProjectQuery {
+        tag: args.flag_value("--tag").map(|tag| tag.to_string()),
+        favorites_only: args.has_flag("--favorites"),
+        sort_by,
+        descending: args.has_flag("--desc"),
+        ..Default::default()
+    })
+}
+
+fn list_projects(query: &ProjectQuery, json: bool) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let config = config_directory_service::get_project_config(&config_directory);
+
+    format_projects(
+        &project_query::query_projects(&config.tracked_projects, query),
+        json,
+    )
+}
+
+fn scan_projects(json: bool) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let scanned_projects = project_service::get_all_projects_from_dirs(&config_directory);
+    let config = config_directory_service::get_project_config(&config_directory);
+
+    let projects = project_service::project_reconciliation(
+        config.tracked_projects,
+        scanned_projects,
+        &installed_engines(&config_directory)?,
+    );
+
+    config_directory_service::save_projects_to_config(&config_directory, &projects);
+
+    format_projects(&projects, json)
+}
+
+fn open_project(
+    project_name: &str,
+    profile_name: Option<&str>,
+    force: bool,
+) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let mut config = config_directory_service::get_project_config(&config_directory);
+
+    let launch = project_launcher::prepare_launch(
+        &config.tracked_projects,
+        &installed_engines(&config_directory)?,
+        project_name,
+        profile_name,
+        None,
+        force,
+    )?;
+
+    let child = launch.command().spawn().map_err(|error| {
+        format!(
+            "Could not start {}: {}",
+            launch.engine.executable_path, error
+        )
+    })?;
+
+    // The CLI is gone by the time the editor exits, so the lock is detected as stale by its process id and
+    // start time instead of being removed, and no usage session is opened since it would never be closed
+    project_launcher::record_launch(
+        &launch,
+        child.id(),
+        Local::now().timestamp_millis(),
+        false,
+        &mut config.tracked_projects,
+        &config_directory,
+    );
+
+    Ok(format!(
+        "Opened {} with {} using profile {}\n",
+        launch.project.project_name, launch.engine.version_name, launch.profile.name
+    ))
+}
+
+fn set_project_engine(project_name: &str, engine_name: &str, json: bool) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let mut config = config_directory_service::get_project_config(&config_directory);
+
+    let engine = installed_engines(&config_directory)?
+        .into_iter()
+        .find(|engine| engine.version_name == engine_name)
+        .ok_or(format!("{} is not installed", engine_name))?;
+
+    let project = config
+        .tracked_projects
+        .iter_mut()
+        .find(|project| project.project_name == project_name)
+        .ok_or(format!("Did not find a project with name {}", project_name))?;
+
+    project.engine_version = engine.version_name.clone();
+    project.engine_valid = true;
+
+    config_directory_service::save_projects_to_config(&config_directory, &config.tracked_projects);
+
+    format_projects(&config.tracked_projects, json)
+}
+
+/// Gets a tracked project and its assigned engine
+fn find_project_with_engine(
+    config_directory: &ConfigDirectoryService,
+    project_name: &str,
+) -> Result<(ProjectData, GodotEngineVersion), String> {
+    let config = config_directory_service::get_project_config(config_directory);
+
+    let project = config
+        .tracked_projects
+        .into_iter()
+        .find(|project| project.project_name == project_name)
+        .ok_or(format!("Did not find a project with name {}", project_name))?;
+
+    let engine = installed_engines(config_directory)?
+        .into_iter()
+        .find(|engine| engine.version_name == project.engine_version)
+        .ok_or(format!(
+            "Did not find a godot engine with name {}",
+            project.engine_version
+        ))?;
+
+    Ok((project, engine))
+}
+
+fn diagnose_project(project_name: &str, json: bool) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let config = config_directory_service::get_project_config(&config_directory);
+
+    let project = config
+        .tracked_projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .ok_or(format!("Did not find a project with name {}", project_name))?;
+
+    let engine = installed_engines(&config_directory)?
+        .into_iter()
+        .find(|engine| engine.version_name == project.engine_version);
+    let last_session =
+        usage_history::read_usage_sessions(&config_directory.history_path(), project_name).pop();
+
+    let diagnostics = project_diagnostics::diagnose_project(
+        &project_service::project_directory(&project.project_path),
+        engine.as_ref(),
+        last_session
+            .as_ref()
+            .map(|session| session.engine_name.as_str()),
+    );
+
+    if json {
+        return to_json(&diagnostics);
+    }
+
+    if diagnostics.is_empty() {
+        return Ok(format!("No problems found in {}\n", project_name));
+    }
+
+    let rows = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            vec![
+                format!("{:?}", diagnostic.severity).to_uppercase(),
+                diagnostic.message.clone(),
+                diagnostic.suggested_fix.clone(),
+            ]
+        })
+        .collect();
+
+    Ok(format_table(&["SEVERITY", "PROBLEM", "FIX"], &rows))
+}
+
+fn upgrade_project(
+    project_name: &str,
+    engine_name: &str,
+    dry_run: bool,
+    json: bool,
+) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let mut config = config_directory_service::get_project_config(&config_directory);
+
+    let engine = installed_engines(&config_directory)?
+        .into_iter()
+        .find(|engine| engine.version_name == engine_name)
+        .ok_or(format!("{} is not installed", engine_name))?;
+
+    let project = config
+        .tracked_projects
+        .iter_mut()
+        .find(|project| project.project_name == project_name)
+        .ok_or(format!("Did not find a project with name {}", project_name))?;
+
+    let report = project_upgrade::upgrade_project(
+        project,
+        &engine,
+        &config_directory.backups_path(),
+        &config.backup_settings,
+        dry_run,
+    )?;
+
+    if !report.dry_run && report.success {
+        project.engine_version = engine.version_name.clone();
+        project.engine_valid = true;
+
+        config_directory_service::save_projects_to_config(
+            &config_directory,
+            &config.tracked_projects,
+        );
+    }
+
+    if json {
+        return to_json(&report);
+    }
+
+    let mut output = report.output.join("\n");
+    output.push('\n');
+
+    if let Some(backup_path) = &report.backup_path {
+        output.push_str(&format!("Backed up {} to {}\n", project_name, backup_path));
+    }
+
+    match (report.dry_run, report.success) {
+        (true, true) => Ok(output),
+        (false, true) => Ok(format!(
+            "{}Converted {} and assigned {}\n",
+            output, project_name, engine_name
+        )),
+        (_, false) => Err(format!("{}Converting {} failed", output, project_name)),
+    }
+}
+
+fn list_export_presets(project_name: &str, json: bool) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let config = config_directory_service::get_project_config(&config_directory);
+
+    let project = config
+        .tracked_projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .ok_or(format!("Did not find a project with name {}", project_name))?;
+
+    let presets = export_preset::read_export_presets(&project_service::project_directory(
+        &project.project_path,
+    ));
+
+    if json {
+        return to_json(&presets);
+    }
+
+    let rows = presets
+        .iter()
+        .map(|preset| {
+            vec![
+                preset.name.clone(),
+                preset.platform.clone(),
+                preset.export_path.clone(),
+            ]
+        })
+        .collect();
+
+    Ok(format_table(&["NAME", "PLATFORM", "EXPORT PATH"], &rows))
+}
+
+/// Runs `task` with the output of the engine going straight to the terminal, and fails if the engine does
+fn run_project_task(project_name: &str, task: ProjectTask) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let (project, engine) = find_project_with_engine(&config_directory, project_name)?;
+
+    if task.imports_resources() {
+        editor_lock::ensure_not_open(
+            &project.project_name,
+            &project_service::project_directory(&project.project_path),
+            None,
+        )?;
+    }
+
+    let status = command::command::build_task_command(&project, &engine, &task)?
+        .status()
+        .map_err(|error| format!("Could not start {}: {}", engine.executable_path, error))?;
+
+    if !status.success() {
+        return Err(format!(
+            "Task on {} failed with exit code {}",
+            project.project_name,
+            status
+                .code()
+                .map(|code| code.to_string())
+                .unwrap_or("unknown".to_string())
+        ));
+    }
+
+    Ok(format!("Task on {} finished\n", project.project_name))
+}
+
+fn list_directories(json: bool) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let config = config_directory_service::get_project_config(&config_directory);
+
+    format_directories(&config.tracked_directories, json)
+}
+
+fn add_directory(directory: &str, json: bool) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let config = config_directory_service::get_project_config(&config_directory);
+    let mut directories = config.tracked_directories;
+
+    if !directories.iter().any(|value| value == directory) {
+        directories.push(directory.to_string());
+    }
+
+    config_directory_service::save_tracked_directories_to_config(&config_directory, &directories);
+
+    format_directories(&directories, json)
+}
+
+fn remove_directory(directory: &str, json: bool) -> Result<String, String> {
+    let config_directory = ConfigDirectoryService::new();
+    let config = config_directory_service::get_project_config(&config_directory);
+    let directories: Vec<String> = config
+        .tracked_directories
+        .into_iter()
+        .filter(|value| value != directory)
+        .collect();
+
+    config_directory_service::save_tracked_directories_to_config(&config_directory, &directories);
+
+    format_directories(&directories, json)
+}
+
+/// Attaches to the console of the terminal the manager was started from.
+/// Release builds on Windows use the GUI subsystem, so output is otherwise discarded.
+#[cfg(windows)]
+pub fn attach_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+pub fn attach_console() {}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_cli_invocation, CliArgs};
+
+    #[test]
+    fn test_is_cli_invocation() {
+        assert!(is_cli_invocation(&[
+            "engines".to_string(),
+            "list".to_string()
+        ]));
+        assert!(is_cli_invocation(&["--help".to_string()]));
+        assert!(!is_cli_invocation(&[]));
+        assert!(!is_cli_invocation(&["-psn_0_12345".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = CliArgs::parse(&[
+            "engines".to_string(),
+            "install".to_string(),
+            "--self-contained".to_string(),
+            "Godot_v4.2.1-stable_win64".to_string(),
+            "--json".to_string(),
+        ]);
+
+        assert!(args.positional == vec!["engines", "install", "Godot_v4.2.1-stable_win64"]);
+        assert!(args.has_flag("--self-contained"));
+        assert!(args.has_flag("--json"));
+        assert!(!args.has_flag("--force"));
+    }
+
+    #[test]
+    fn test_flag_value() {
+        let args = CliArgs::parse(&[
+            "projects".to_string(),
+            "open".to_string(),
+            "my_game".to_string(),
+            "--profile=Verbose".to_string(),
+        ]);
+
+        assert!(args.flag_value("--profile") == Some("Verbose"));
+        assert!(args.flag_value("--json").is_none());
+    }
+}
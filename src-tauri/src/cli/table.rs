@@ -0,0 +1,60 @@
+/// Formats rows as a plain text table with a header row, padding every column to its widest value
+pub fn format_table(headers: &[&str], rows: &Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.len()).collect();
+
+    for row in rows {
+        for (index, value) in row.iter().enumerate() {
+            if index < widths.len() {
+                widths[index] = widths[index].max(value.chars().count());
+            }
+        }
+    }
+
+    let mut table = format_row(
+        &headers.iter().map(|header| header.to_string()).collect(),
+        &widths,
+    );
+    table.push_str(&format_row(
+        &widths.iter().map(|width| "-".repeat(*width)).collect(),
+        &widths,
+    ));
+
+    for row in rows {
+        table.push_str(&format_row(row, &widths));
+    }
+
+    table
+}
+
+fn format_row(values: &Vec<String>, widths: &Vec<usize>) -> String {
+    let columns: Vec<String> = widths
+        .iter()
+        .enumerate()
+        .map(|(index, width)| {
+            let value = values.get(index).map(|value| value.as_str()).unwrap_or("");
+            format!("{:<width$}", value, width = width)
+        })
+        .collect();
+
+    format!("{}\n", columns.join("  ").trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_table;
+
+    #[test]
+    fn test_format_table() {
+        let rows = vec![
+            vec!["Godot_v4.2.1-stable_win64".to_string(), "4.2.1".to_string()],
+            vec!["Godot_v3.5.3-stable_win64".to_string(), "3.5.3".to_string()],
+        ];
+
+        let table = format_table(&["NAME", "VERSION"], &rows);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert!(lines.len() == 4);
+        assert!(lines[0] == "NAME                       VERSION");
+        assert!(lines[2] == "Godot_v4.2.1-stable_win64  4.2.1");
+    }
+}
@@ -1,2 +0,0 @@
-pub mod download_service;
-pub mod os_type;
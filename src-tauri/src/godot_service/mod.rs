@@ -1,2 +0,0 @@
-pub mod godot_engine_service;
-pub mod godot_engine_version;
@@ -1,30 +1,78 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::collections::HashMap;
+mod cli;
+
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use chrono::{DateTime, Local};
-use directory::config_directory_service::{self, ConfigDirectoryService};
-use fetcher::download_service::{self, filter_assets_by_name};
-use godot_service::{godot_engine_service, godot_engine_version::GodotEngineVersion};
-use news::news::{get_news, NewsEntry};
-use project::{
-    project_data::ProjectData,
-    project_service::{self, ProjectDirectoryService},
+use godot_manager_core::{
+    command::{
+        self,
+        command::{ProjectTask, WindowMode},
+    },
+    directory::{
+        config_directory_service::{self, ConfigDirectoryService},
+        storage_report::{self, StorageReport},
+    },
+    fetcher::{
+        download_service,
+        progress_reporter::DownloadProgress,
+        release_notes::{self, ReleaseNotes},
+    },
+    godot_service::{
+        dotnet_service::{self, DotnetStatus},
+        engine_updates::{self, EngineUpdate},
+        export_template_service, godot_engine_service,
+        godot_engine_version::GodotEngineVersion,
+        release_channels::{self, ReleaseFilter, ReleaseFlavor, ReleaseGroup},
+    },
+    news::news_cache::{self, NewsResponse, NewsSettings},
+    process::{
+        editor_lock, process_log,
+        process_registry::{
+            ProcessInfo, ProcessKind, ProcessListener, ProcessOutput, ProcessRegistry,
+        },
+    },
+    project::{
+        export_preset::{self, ExportPreset},
+        launch_profile::{self, LaunchProfile},
+        project_backup::{self, BackupSettings, ProjectBackup},
+        project_cache::{self, CacheCleanResult, ProjectCache},
+        project_data::{ProjectData, ProjectMetadata},
+        project_diagnostics::{self, Diagnostic},
+        project_launcher,
+        project_query::{self, ProjectQuery},
+        project_service::{self, ProjectDirectoryService},
+        project_thumbnail::{self, ProjectThumbnail},
+        project_upgrade::{self, UpgradeReport},
+        usage_history::{self, ProjectStats},
+    },
 };
 use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
 use tokio::sync::Mutex;
 
-mod command;
-mod directory;
-mod environmnet;
-mod fetcher;
-mod godot_service;
-pub mod news;
-mod project;
-mod test_data;
+pub struct DataState(
+    Mutex<Data>,
+    DownloadProgress,
+    std::sync::Mutex<ProcessRegistry>,
+    /// Held while the news cache is changed, shared with the background refresh
+    Arc<Mutex<()>>,
+);
+
+/// Forwards the output and exit of launched editors and games to the frontend as events
+struct EventProcessListener(AppHandle);
+
+impl ProcessListener for EventProcessListener {
+    fn output(&self, output: &ProcessOutput) {
+        self.0.emit("process-output", output.clone()).ok();
+    }
 
-pub struct DataState(Mutex<Data>, Mutex<HashMap<String, usize>>);
+    fn exited(&self, process: &ProcessInfo) {
+        self.0.emit("process-exited", process.clone()).ok();
+    }
+}
 
 pub struct Data {
     all_godot_engine_versions: Vec<GodotEngineVersion>,
@@ -36,20 +84,24 @@ pub struct Data {
 struct GodotEngineVersionResponse {
     #[serde(rename(serialize = "installedVersions"))]
     pub installed_godot_versions: Vec<GodotEngineVersion>,
+    /// The catalog engines in the visible release channels and flavors
     #[serde(rename(serialize = "allVersions"))]
     pub all_godot_versions: Vec<GodotEngineVersion>,
+    /// The visible catalog engines grouped by channel and flavor
+    pub groups: Vec<ReleaseGroup>,
+    /// Newer releases for the installed engines found in the refreshed catalog
+    pub updates: Vec<EngineUpdate>,
 }
 
 #[tauri::command]
 async fn get_engine_versions(
     state: tauri::State<'_, DataState>,
 ) -> Result<GodotEngineVersionResponse, ()> {
-    let releases = download_service::get_available_releases().await.unwrap();
-    let assets = filter_assets_by_name(&releases, "win64");
-    let all_godot_versions: Vec<GodotEngineVersion> = assets
-        .into_iter()
-        .map(|asset| godot_engine_service::from_asset(asset))
-        .collect();
+    let config_directory = ConfigDirectoryService::new();
+    let all_godot_versions =
+        download_service::get_engine_catalog(&reqwest::Client::new(), &config_directory)
+            .await
+            .map_err(|error| println!("Could not get the engine catalog: {}", error))?;
 
     let mut state_guard = state.0.lock().await;
     *state_guard = Data {
@@ -58,9 +110,128 @@ async fn get_engine_versions(
         projects: state_guard.projects.clone(),
     };
 
+    let updates = engine_updates::find_engine_updates(
+        &state_guard.installed_godot_engine_versions,
+        &all_godot_versions,
+    );
+
+    let release_filter =
+        config_directory_service::get_project_config(&config_directory).release_filter;
+    let visible_versions = release_channels::filter_catalog(&all_godot_versions, &release_filter);
+
     Ok(GodotEngineVersionResponse {
         installed_godot_versions: vec![],
-        all_godot_versions,
+        groups: release_channels::group_catalog(&visible_versions),
+        all_godot_versions: visible_versions,
+        updates,
+    })
+}
+
+#[tauri::command]
+async fn get_release_filter() -> Result<ReleaseFilter, ()> {
+    let config_directory = ConfigDirectoryService::new();
+
+    Ok(config_directory_service::get_project_config(&config_directory).release_filter)
+}
+
+#[tauri::command]
+async fn set_release_filter(release_filter: ReleaseFilter) -> Result<ReleaseFilter, ()> {
+    let config_directory = ConfigDirectoryService::new();
+
+    config_directory_service::save_release_filter_to_config(&config_directory, &release_filter);
+
+    Ok(release_filter)
+}
+
+/// Gets the newest stable engine of the last fetched release catalog, the .NET build if `mono` is set
+#[tauri::command]
+async fn get_latest_stable(
+    state: tauri::State<'_, DataState>,
+    mono: bool,
+) -> Result<Option<GodotEngineVersion>, ()> {
+    let state_guard = state.0.lock().await;
+    let flavor = if mono {
+        ReleaseFlavor::Mono
+    } else {
+        ReleaseFlavor::Standard
+    };
+
+    Ok(release_channels::latest_stable(&state_guard.all_godot_engine_versions, flavor).cloned())
+}
+
+/// Gets the notes of an engine release, from the release cache when offline
+#[tauri::command]
+async fn get_release_notes(version: String) -> Result<ReleaseNotes, String> {
+    let config_directory = ConfigDirectoryService::new();
+
+    release_notes::get_release_notes(&reqwest::Client::new(), &config_directory, &version).await
+}
+
+/// Gets the updates for the installed engines from the last fetched release catalog
+#[tauri::command]
+async fn get_engine_updates(state: tauri::State<'_, DataState>) -> Result<Vec<EngineUpdate>, ()> {
+    let state_guard = state.0.lock().await;
+
+    Ok(engine_updates::find_engine_updates(
+        &state_guard.installed_godot_engine_versions,
+        &state_guard.all_godot_engine_versions,
+    ))
+}
+
+#[derive(Serialize, Deserialize)]
+struct EngineUpgradeResponse {
+    /// The newly installed engine
+    pub engine: GodotEngineVersion,
+    /// The projects that were moved to the new engine
+    pub reassigned: Vec<String>,
+    #[serde(rename(serialize = "installedVersions"))]
+    pub installed_versions: Vec<GodotEngineVersion>,
+    pub projects: Vec<ProjectData>,
+}
+
+/// Installs the update for an installed engine next to it. If `reassign_projects` is set the projects using
+/// the old engine are moved to the new one, except projects that pinned their engine.
+#[tauri::command]
+async fn upgrade_engine(
+    state: tauri::State<'_, DataState>,
+    engine_name: String,
+    reassign_projects: bool,
+) -> Result<EngineUpgradeResponse, String> {
+    let directory_service = ConfigDirectoryService::new();
+    let installed_versions = godot_engine_service::get_installed_godot_versions(&directory_service);
+    let catalog = state.0.lock().await.all_godot_engine_versions.clone();
+
+    let upgraded_engine = engine_updates::upgrade_engine(
+        &reqwest::Client::new(),
+        &directory_service,
+        &engine_name,
+        &installed_versions,
+        &catalog,
+        &state.1,
+    )
+    .await?;
+
+    let mut state_guard = state.0.lock().await;
+
+    let reassigned = if reassign_projects {
+        engine_updates::reassign_to_upgrade(
+            &mut state_guard.projects,
+            &engine_name,
+            &upgraded_engine,
+            &directory_service,
+        )
+    } else {
+        vec![]
+    };
+    let projects = state_guard.projects.clone();
+
+    drop(state_guard);
+
+    Ok(EngineUpgradeResponse {
+        engine: upgraded_engine,
+        reassigned,
+        installed_versions: get_installed_versions(state).await.unwrap_or_default(),
+        projects,
     })
 }
 
@@ -68,7 +239,11 @@ async fn get_engine_versions(
 async fn download_engine_version(
     state: tauri::State<'_, DataState>,
     engine_name: String,
+    self_contained: Option<bool>,
 ) -> Result<(), ()> {
+    godot_engine_service::validate_engine_name(&engine_name)
+        .map_err(|error| println!("{}", error))?;
+
     let state_guard = state.0.lock().await;
 
     if let Some(_) = state_guard
@@ -91,10 +266,15 @@ async fn download_engine_version(
     // download
     let directory_service = ConfigDirectoryService::new();
 
-    let updated_engine =
-        download_service::download_and_extract_engine(&directory_service, &engine, &state)
-            .await
-            .unwrap();
+    let updated_engine = download_service::download_and_extract_engine(
+        &reqwest::Client::new(),
+        &directory_service,
+        &engine,
+        self_contained.unwrap_or(false),
+        &state.1,
+    )
+    .await
+    .unwrap();
 
     let mut state_guard = state.0.lock().await;
 
@@ -113,14 +293,14 @@ async fn get_installed_versions(
     let directory_service = ConfigDirectoryService::new();
     let installed_versions = godot_engine_service::get_installed_godot_versions(&directory_service);
 
-    let guard = state.1.lock().await;
-
     // Don't include versions actively downloading
-    let filtered: Vec<GodotEngineVersion> = installed_versions
-        .into_iter()
-        .filter(|engine| !guard.contains_key(&engine.version_name))
-        .collect();
-    drop(guard);
+    let filtered: Vec<GodotEngineVersion> = {
+        let guard = state.1.lock().unwrap();
+        installed_versions
+            .into_iter()
+            .filter(|engine| !guard.contains_key(&engine.version_name))
+            .collect()
+    };
 
     let mut state_guard = state.0.lock().await;
     state_guard.installed_godot_engine_versions = filtered.clone();
@@ -128,33 +308,128 @@ async fn get_installed_versions(
     Ok(filtered)
 }
 
+#[derive(Serialize, Deserialize)]
+struct EngineRemovalResponse {
+    /// Whether the engine was removed. It is kept if projects depend on it and removal was not forced.
+    pub removed: bool,
+    /// The projects that were assigned to the engine
+    pub dependents: Vec<String>,
+    #[serde(rename(serialize = "installedVersions"))]
+    pub installed_versions: Vec<GodotEngineVersion>,
+    pub projects: Vec<ProjectData>,
+}
+
+/// Removes an installed engine. If projects are assigned to it nothing is removed unless `force` is set,
+/// in which case they are assigned `reassign_to`, or marked as having an invalid engine if it isn't given.
 #[tauri::command]
 async fn remove_installed_version(
     state: tauri::State<'_, DataState>,
     engine_version_name: String,
-) -> Result<Vec<GodotEngineVersion>, ()> {
+    force: Option<bool>,
+    reassign_to: Option<String>,
+) -> Result<EngineRemovalResponse, String> {
     let directory_service = ConfigDirectoryService::new();
     let installed_versions = godot_engine_service::get_installed_godot_versions(&directory_service);
 
-    let engine_version = installed_versions
+    let mut state_guard = state.0.lock().await;
+    let removal = godot_engine_service::remove_engine(
+        &engine_version_name,
+        &installed_versions,
+        &mut state_guard.projects,
+        force.unwrap_or(false),
+        reassign_to.as_deref(),
+        &directory_service,
+    )?;
+    let projects = state_guard.projects.clone();
+
+    if !removal.removed {
+        return Ok(EngineRemovalResponse {
+            removed: false,
+            dependents: removal.dependents,
+            installed_versions: state_guard.installed_godot_engine_versions.clone(),
+            projects,
+        });
+    }
+
+    drop(state_guard);
+
+    Ok(EngineRemovalResponse {
+        removed: true,
+        dependents: removal.dependents,
+        installed_versions: get_installed_versions(state).await.unwrap_or_default(),
+        projects,
+    })
+}
+
+#[tauri::command]
+async fn set_engine_self_contained(
+    state: tauri::State<'_, DataState>,
+    engine_name: String,
+    self_contained: bool,
+    migrate_settings: bool,
+) -> Result<Vec<GodotEngineVersion>, ()> {
+    let state_guard = state.0.lock().await;
+
+    let engine = state_guard
+        .installed_godot_engine_versions
         .iter()
-        .find(|engine| engine.version_name == engine_version_name)
-        .unwrap();
+        .find(|engine| engine.version_name == engine_name)
+        .expect(format!("Did not find a godot engine with name {}", &engine_name).as_str())
+        .clone();
+
+    drop(state_guard);
+
+    godot_engine_service::set_self_contained(&engine, self_contained, migrate_settings).map_err(
+        |error| {
+            println!(
+                "Could not change self-contained mode for {}: {}",
+                engine_name, error
+            )
+        },
+    )?;
+
+    get_installed_versions(state).await
+}
+
+#[tauri::command]
+async fn install_export_templates(
+    state: tauri::State<'_, DataState>,
+    engine_name: String,
+) -> Result<Vec<GodotEngineVersion>, ()> {
+    let state_guard = state.0.lock().await;
 
-    let result =
-        godot_engine_service::remove_installed_version(engine_version, &directory_service).unwrap();
+    let engine = state_guard
+        .installed_godot_engine_versions
+        .iter()
+        .find(|engine| engine.version_name == engine_name)
+        .expect(format!("Did not find a godot engine with name {}", &engine_name).as_str())
+        .clone();
 
-    let new_installed_versions = get_installed_versions(state).await?;
+    drop(state_guard);
 
-    // TODO handle errors
+    let directory_service = ConfigDirectoryService::new();
 
-    Ok(new_installed_versions)
+    export_template_service::install_export_templates(
+        &reqwest::Client::new(),
+        &directory_service,
+        &engine,
+        &state.1,
+    )
+    .await
+    .map_err(|error| {
+        println!(
+            "Could not install export templates for {}: {}",
+            engine_name, error
+        )
+    })?;
+
+    get_installed_versions(state).await
 }
 
 #[tauri::command]
 async fn get_all_projects(state: tauri::State<'_, DataState>) -> Result<Vec<ProjectData>, ()> {
     let config_directory = ConfigDirectoryService::new();
-    let scanned_projects = get_all_projects_from_dirs(&config_directory);
+    let scanned_projects = project_service::get_all_projects_from_dirs(&config_directory);
     let config = config_directory_service::get_project_config(&config_directory);
     let mut state_guard = state.0.lock().await;
 
@@ -169,17 +444,62 @@ async fn get_all_projects(state: tauri::State<'_, DataState>) -> Result<Vec<Proj
     Ok(projects)
 }
 
-fn get_all_projects_from_dirs(config_directory: &ConfigDirectoryService) -> Vec<ProjectData> {
-    let config = config_directory_service::get_project_config(&config_directory);
-    let mut all_projects: Vec<ProjectData> = vec![];
+#[tauri::command]
+async fn query_projects(
+    state: tauri::State<'_, DataState>,
+    query: ProjectQuery,
+) -> Result<Vec<ProjectData>, ()> {
+    let state_guard = state.0.lock().await;
+    Ok(project_query::query_projects(&state_guard.projects, &query))
+}
 
-    for directory in config.tracked_directories {
-        let project_service = ProjectDirectoryService::new(&directory);
-        let mut projects = project_service.find_projects();
-        all_projects.append(&mut projects);
-    }
+#[tauri::command]
+async fn get_project_tags(state: tauri::State<'_, DataState>) -> Result<Vec<String>, ()> {
+    let state_guard = state.0.lock().await;
+    Ok(project_query::all_tags(&state_guard.projects))
+}
+
+#[tauri::command]
+async fn set_project_metadata(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+    metadata: ProjectMetadata,
+) -> Result<Vec<ProjectData>, ()> {
+    let mut state_guard = state.0.lock().await;
+
+    state_guard
+        .projects
+        .iter_mut()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str())
+        .metadata = metadata;
+
+    let config_directory = ConfigDirectoryService::new();
+    config_directory_service::save_projects_to_config(&config_directory, &state_guard.projects);
+    Ok(state_guard.projects.clone())
+}
+
+/// Gets the icon and editor screenshot of every project, keyed by project name
+#[tauri::command]
+async fn get_project_thumbnails(
+    state: tauri::State<'_, DataState>,
+) -> Result<HashMap<String, ProjectThumbnail>, ()> {
+    let state_guard = state.0.lock().await;
+    let config_directory = ConfigDirectoryService::new();
 
-    all_projects
+    Ok(state_guard
+        .projects
+        .iter()
+        .map(|project| {
+            (
+                project.project_name.clone(),
+                project_thumbnail::get_project_thumbnail(
+                    &config_directory.thumbnail_cache_path(),
+                    &project_service::project_directory(&project.project_path),
+                ),
+            )
+        })
+        .collect())
 }
 
 #[tauri::command]
@@ -260,7 +580,7 @@ async fn set_engine_version_for_project(
 async fn poll_download_status_list(
     state: tauri::State<'_, DataState>,
 ) -> Result<Vec<(String, usize)>, ()> {
-    let state_guard = state.1.lock().await;
+    let state_guard = state.1.lock().unwrap();
     let pairs = state_guard
         .iter()
         .map(|(name, score)| (name.clone(), score.clone()))
@@ -271,10 +591,75 @@ async fn poll_download_status_list(
 
 #[tauri::command]
 async fn open_project(
+    app: AppHandle,
     state: tauri::State<'_, DataState>,
     project_name: String,
-) -> Result<(String, i64), ()> {
+    profile_name: Option<String>,
+    force: Option<bool>,
+) -> Result<(String, i64), String> {
     let mut state_guard = state.0.lock().await;
+    let config_directory = ConfigDirectoryService::new();
+    let mut registry = state.2.lock().unwrap();
+
+    let launch = project_launcher::prepare_launch(
+        &state_guard.projects,
+        &state_guard.installed_godot_engine_versions,
+        &project_name,
+        profile_name.as_deref(),
+        registry
+            .running_editor(&project_name)
+            .map(|process| process.id),
+        force.unwrap_or(false),
+    )?;
+
+    let (kind, listener) = if launch.is_editor() {
+        (
+            ProcessKind::Editor,
+            launch.editor_listener(&config_directory, Arc::new(EventProcessListener(app))),
+        )
+    } else {
+        (
+            ProcessKind::Game,
+            Arc::new(EventProcessListener(app)) as Arc<dyn ProcessListener>,
+        )
+    };
+
+    let process = registry
+        .spawn(
+            launch.command(),
+            Some(&launch.project.project_name),
+            &launch.engine.version_name,
+            kind,
+            &config_directory.logs_path(),
+            listener,
+        )
+        .map_err(|error| {
+            format!(
+                "Could not start {}: {}",
+                launch.engine.executable_path, error
+            )
+        })?;
+
+    drop(registry);
+
+    project_launcher::record_launch(
+        &launch,
+        process.id,
+        process.started_at,
+        true,
+        &mut state_guard.projects,
+        &config_directory,
+    );
+
+    Ok((launch.project.project_name, process.started_at))
+}
+
+#[tauri::command]
+async fn get_project_scenes(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+) -> Result<Vec<String>, ()> {
+    let state_guard = state.0.lock().await;
 
     let project = state_guard
         .projects
@@ -282,6 +667,29 @@ async fn open_project(
         .find(|project| project.project_name == project_name)
         .expect(format!("Did not find a project with name {}", project_name).as_str());
 
+    Ok(project_service::find_project_scenes(
+        &project_service::project_directory(&project.project_path),
+    ))
+}
+
+#[tauri::command]
+async fn run_project(
+    app: AppHandle,
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+    scene: Option<String>,
+    window_mode: WindowMode,
+    profile_name: Option<String>,
+) -> Result<ProcessInfo, ()> {
+    let state_guard = state.0.lock().await;
+
+    let project = state_guard
+        .projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str())
+        .clone();
+
     let godot_engine = state_guard
         .installed_godot_engine_versions
         .iter()
@@ -292,126 +700,811 @@ async fn open_project(
                 project.engine_version
             )
             .as_str(),
-        );
-
-    let p = project.clone();
-    let g = godot_engine.clone();
+        )
+        .clone();
 
-    let time = Local::now().timestamp_millis();
+    drop(state_guard);
 
-    state_guard
-        .projects
-        .iter_mut()
-        .find(|project| project.project_name == project_name)
-        .expect(format!("Did not find a project with name {}", project_name).as_str())
-        .last_date_opened = time.clone();
+    let profile = launch_profile::resolve_launch_profile(&project, profile_name.as_deref())
+        .map_err(|error| println!("{}", error))?;
 
     let config_directory = ConfigDirectoryService::new();
-    config_directory_service::save_projects_to_config(&config_directory, &state_guard.projects);
 
-    drop(state_guard);
+    state
+        .2
+        .lock()
+        .unwrap()
+        .spawn(
+            command::command::build_run_command(
+                &project,
+                &godot_engine,
+                &profile,
+                scene.as_deref(),
+                &window_mode,
+            ),
+            Some(&project.project_name),
+            &godot_engine.version_name,
+            ProcessKind::Game,
+            &config_directory.logs_path(),
+            Arc::new(EventProcessListener(app)),
+        )
+        .map_err(|error| {
+            println!(
+                "Could not start {}: {}",
+                godot_engine.executable_path, error
+            )
+        })
+}
+
+#[tauri::command]
+async fn get_export_presets(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+) -> Result<Vec<ExportPreset>, ()> {
+    let state_guard = state.0.lock().await;
 
-    command::command::open_project(&p, &g);
+    let project = state_guard
+        .projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str());
 
-    Ok((p.project_name.clone(), time))
+    Ok(export_preset::read_export_presets(
+        &project_service::project_directory(&project.project_path),
+    ))
 }
 
+/// Starts `task` on the project with its assigned engine. Output is streamed through the `process-output` event
+/// and the result through `process-exited`.
 #[tauri::command]
-async fn open_engine(state: tauri::State<'_, DataState>, engine_name: String) -> Result<(), ()> {
-    let mut state_guard = state.0.lock().await;
+async fn run_project_task(
+    app: AppHandle,
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+    task: ProjectTask,
+) -> Result<ProcessInfo, String> {
+    let state_guard = state.0.lock().await;
+
+    let project = state_guard
+        .projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .ok_or(format!("Did not find a project with name {}", project_name))?
+        .clone();
 
     let godot_engine = state_guard
         .installed_godot_engine_versions
         .iter()
-        .find(|engine| engine.version_name == engine_name)
-        .expect(format!("Did not find a godot engine with name {}", &engine_name).as_str());
+        .find(|engine| engine.version_name == project.engine_version)
+        .ok_or(format!(
+            "Did not find a godot engine with name {}",
+            project.engine_version
+        ))?
+        .clone();
 
-    let g = godot_engine.clone();
+    drop(state_guard);
 
+    let command = command::command::build_task_command(&project, &godot_engine, &task)?;
     let config_directory = ConfigDirectoryService::new();
-    config_directory_service::save_projects_to_config(&config_directory, &state_guard.projects);
+    let mut registry = state.2.lock().unwrap();
+
+    if task.imports_resources() {
+        editor_lock::ensure_not_open(
+            &project.project_name,
+            &project_service::project_directory(&project.project_path),
+            registry
+                .running_editor(&project.project_name)
+                .map(|process| process.id),
+        )?;
+    }
 
-    drop(state_guard);
+    registry
+        .spawn(
+            command,
+            Some(&project.project_name),
+            &godot_engine.version_name,
+            ProcessKind::Task,
+            &config_directory.logs_path(),
+            Arc::new(EventProcessListener(app)),
+        )
+        .map_err(|error| {
+            format!(
+                "Could not start {}: {}",
+                godot_engine.executable_path, error
+            )
+        })
+}
 
-    command::command::open_engine(&g);
+#[tauri::command]
+async fn get_processes(state: tauri::State<'_, DataState>) -> Result<Vec<ProcessInfo>, ()> {
+    Ok(state.2.lock().unwrap().processes())
+}
 
-    Ok(())
+#[tauri::command]
+async fn get_process_log(
+    state: tauri::State<'_, DataState>,
+    id: u32,
+    max_lines: usize,
+) -> Result<Vec<String>, ()> {
+    let process = state
+        .2
+        .lock()
+        .unwrap()
+        .get(id)
+        .ok_or_else(|| println!("Did not find a process with id {}", id))?;
+
+    process_log::tail_log(&PathBuf::from(&process.log_path), max_lines)
+        .map_err(|error| println!("Could not read log {}: {}", process.log_path, error))
 }
 
 #[tauri::command]
-async fn get_news_entries(state: tauri::State<'_, DataState>) -> Result<Vec<NewsEntry>, ()> {
-    let news_entries = get_news().await.unwrap();
-    Ok(news_entries)
+async fn clear_exited_processes(
+    state: tauri::State<'_, DataState>,
+) -> Result<Vec<ProcessInfo>, ()> {
+    let mut registry = state.2.lock().unwrap();
+    registry.clear_exited();
+    Ok(registry.processes())
 }
 
-fn main() {
-    let state = DataState(
-        Mutex::new(Data {
-            all_godot_engine_versions: vec![],
-            installed_godot_engine_versions: vec![],
-            projects: vec![],
-        }),
-        Mutex::new(HashMap::new()),
-    );
+#[tauri::command]
+async fn stop_process(state: tauri::State<'_, DataState>, id: u32) -> Result<(), ()> {
+    state
+        .2
+        .lock()
+        .unwrap()
+        .stop(id)
+        .map_err(|error| println!("{}", error))
+}
 
-    tauri::Builder::default()
-        .plugin(tauri_plugin_dialog::init())
-        .plugin(tauri_plugin_shell::init())
-        .manage(state)
-        .invoke_handler(tauri::generate_handler![
-            get_engine_versions,
-            download_engine_version,
-            get_installed_versions,
-            remove_installed_version,
-            get_all_projects,
-            save_project_path,
-            get_project_paths,
-            remove_project_path,
-            set_engine_version_for_project,
-            poll_download_status_list,
-            open_project,
-            open_engine,
-            get_news_entries,
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+#[tauri::command]
+async fn get_project_stats(project_name: String) -> Result<ProjectStats, ()> {
+    let config_directory = ConfigDirectoryService::new();
+    let sessions =
+        usage_history::read_usage_sessions(&config_directory.history_path(), &project_name);
+
+    Ok(usage_history::project_stats(
+        sessions,
+        Local::now().timestamp_millis(),
+    ))
+}
+
+#[tauri::command]
+async fn get_launch_profiles(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+) -> Result<Vec<LaunchProfile>, ()> {
+    let state_guard = state.0.lock().await;
+
+    let project = state_guard
+        .projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str());
+
+    let mut profiles = project.launch_profiles.clone();
+    if !profiles
+        .iter()
+        .any(|profile| profile.name == launch_profile::DEFAULT_PROFILE_NAME)
+    {
+        profiles.insert(0, LaunchProfile::default_editor());
+    }
+
+    Ok(profiles)
+}
+
+#[tauri::command]
+async fn save_launch_profile(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+    profile: LaunchProfile,
+) -> Result<Vec<ProjectData>, ()> {
+    let mut state_guard = state.0.lock().await;
+
+    let project = state_guard
+        .projects
+        .iter_mut()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str());
+
+    launch_profile::save_launch_profile(project, profile);
+
+    let config_directory = ConfigDirectoryService::new();
+    config_directory_service::save_projects_to_config(&config_directory, &state_guard.projects);
+    Ok(state_guard.projects.clone())
+}
+
+#[tauri::command]
+async fn remove_launch_profile(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+    profile_name: String,
+) -> Result<Vec<ProjectData>, ()> {
+    let mut state_guard = state.0.lock().await;
+
+    let project = state_guard
+        .projects
+        .iter_mut()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str());
+
+    launch_profile::remove_launch_profile(project, &profile_name);
+
+    let config_directory = ConfigDirectoryService::new();
+    config_directory_service::save_projects_to_config(&config_directory, &state_guard.projects);
+    Ok(state_guard.projects.clone())
+}
+
+#[tauri::command]
+async fn set_default_launch_profile(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+    profile_name: Option<String>,
+) -> Result<Vec<ProjectData>, ()> {
+    let mut state_guard = state.0.lock().await;
+
+    let project = state_guard
+        .projects
+        .iter_mut()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str());
+
+    if let Some(name) = &profile_name {
+        launch_profile::resolve_launch_profile(project, Some(name))
+            .map_err(|error| println!("{}", error))?;
+    }
+    project.default_launch_profile = profile_name;
+
+    let config_directory = ConfigDirectoryService::new();
+    config_directory_service::save_projects_to_config(&config_directory, &state_guard.projects);
+    Ok(state_guard.projects.clone())
+}
+
+#[tauri::command]
+async fn open_engine(
+    app: AppHandle,
+    state: tauri::State<'_, DataState>,
+    engine_name: String,
+) -> Result<ProcessInfo, ()> {
+    let state_guard = state.0.lock().await;
+
+    let godot_engine = state_guard
+        .installed_godot_engine_versions
+        .iter()
+        .find(|engine| engine.version_name == engine_name)
+        .expect(format!("Did not find a godot engine with name {}", &engine_name).as_str());
+
+    let g = godot_engine.clone();
+
+    drop(state_guard);
+
+    let config_directory = ConfigDirectoryService::new();
+
+    state
+        .2
+        .lock()
+        .unwrap()
+        .spawn(
+            command::command::build_engine_command(&g),
+            None,
+            &g.version_name,
+            ProcessKind::Editor,
+            &config_directory.logs_path(),
+            Arc::new(EventProcessListener(app)),
+        )
+        .map_err(|error| println!("Could not start {}: {}", g.executable_path, error))
+}
+
+#[tauri::command]
+async fn check_project_compatibility(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+) -> Result<Vec<String>, ()> {
+    let state_guard = state.0.lock().await;
+
+    let project = state_guard
+        .projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str())
+        .clone();
+
+    let godot_engine = state_guard
+        .installed_godot_engine_versions
+        .iter()
+        .find(|engine| engine.version_name == project.engine_version)
+        .cloned();
+
+    drop(state_guard);
+
+    let Some(godot_engine) = godot_engine else {
+        return Ok(vec![]);
+    };
+
+    Ok(project_service::project_compatibility_warnings(
+        &project,
+        &godot_engine,
+    ))
+}
+
+#[tauri::command]
+async fn diagnose_project(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+) -> Result<Vec<Diagnostic>, ()> {
+    let state_guard = state.0.lock().await;
+
+    let project = state_guard
+        .projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str())
+        .clone();
+
+    let godot_engine = state_guard
+        .installed_godot_engine_versions
+        .iter()
+        .find(|engine| engine.version_name == project.engine_version)
+        .cloned();
+
+    drop(state_guard);
+
+    let config_directory = ConfigDirectoryService::new();
+    let last_session =
+        usage_history::read_usage_sessions(&config_directory.history_path(), &project_name).pop();
+
+    Ok(project_diagnostics::diagnose_project(
+        &project_service::project_directory(&project.project_path),
+        godot_engine.as_ref(),
+        last_session
+            .as_ref()
+            .map(|session| session.engine_name.as_str()),
+    ))
+}
+
+/// Converts a Godot 3 project with the Godot 4 engine `engine_name`. Unless it is a dry run the project is backed up first,
+/// and the engine is assigned to the project once the conversion succeeds.
+#[tauri::command]
+async fn upgrade_project(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+    engine_name: String,
+    dry_run: bool,
+) -> Result<UpgradeReport, String> {
+    let state_guard = state.0.lock().await;
+
+    let project = state_guard
+        .projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str())
+        .clone();
+
+    let godot_engine = state_guard
+        .installed_godot_engine_versions
+        .iter()
+        .find(|engine| engine.version_name == engine_name)
+        .ok_or(format!(
+            "Did not find a godot engine with name {}",
+            engine_name
+        ))?
+        .clone();
+
+    drop(state_guard);
+
+    let config_directory = ConfigDirectoryService::new();
+    let backups_path = config_directory.backups_path();
+    let backup_settings =
+        config_directory_service::get_project_config(&config_directory).backup_settings;
+    let upgrade_engine = godot_engine.clone();
+
+    let report = tokio::task::spawn_blocking(move || {
+        project_upgrade::upgrade_project(
+            &project,
+            &upgrade_engine,
+            &backups_path,
+            &backup_settings,
+            dry_run,
+        )
+    })
+    .await
+    .map_err(|error| error.to_string())??;
+
+    if !report.dry_run && report.success {
+        let mut state_guard = state.0.lock().await;
+
+        if let Some(project) = state_guard
+            .projects
+            .iter_mut()
+            .find(|project| project.project_name == project_name)
+        {
+            project.engine_version = godot_engine.version_name.clone();
+            project.engine_valid = true;
+        }
+
+        config_directory_service::save_projects_to_config(&config_directory, &state_guard.projects);
+    }
+
+    Ok(report)
+}
+
+#[tauri::command]
+async fn create_project_backup(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+) -> Result<ProjectBackup, String> {
+    let state_guard = state.0.lock().await;
+
+    let project = state_guard
+        .projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str())
+        .clone();
+
+    drop(state_guard);
+
+    let config_directory = ConfigDirectoryService::new();
+    let backup_settings =
+        config_directory_service::get_project_config(&config_directory).backup_settings;
+    let backups_path = config_directory.backups_path();
+
+    tokio::task::spawn_blocking(move || {
+        project_backup::create_backup(&project, &backups_path, &backup_settings)
+            .map_err(|error| format!("Could not back up {}: {}", project.project_name, error))
+    })
+    .await
+    .map_err(|error| error.to_string())?
+}
+
+#[tauri::command]
+async fn get_project_backups(project_name: String) -> Result<Vec<ProjectBackup>, ()> {
+    let config_directory = ConfigDirectoryService::new();
+
+    Ok(project_backup::list_backups(
+        &config_directory.backups_path(),
+        &project_name,
+    ))
 }
 
-mod tests {
+/// Restores a backup into its project folder. Refused while an editor has the project open, since the editor would
+/// overwrite the restored files when it saves.
+#[tauri::command]
+async fn restore_project_backup(
+    state: tauri::State<'_, DataState>,
+    project_name: String,
+    backup_id: String,
+) -> Result<(), String> {
+    let state_guard = state.0.lock().await;
 
-    use std::fs;
+    let project = state_guard
+        .projects
+        .iter()
+        .find(|project| project.project_name == project_name)
+        .expect(format!("Did not find a project with name {}", project_name).as_str())
+        .clone();
 
-    use tokio::sync::Mutex;
+    drop(state_guard);
 
-    use crate::{
-        directory::config_directory_service::{self, ConfigDirectoryService},
-        get_all_projects_from_dirs,
-        project::{project_data::ProjectConfig, project_service::ProjectDirectoryService},
+    let config_directory = ConfigDirectoryService::new();
+    let backup =
+        project_backup::find_backup(&config_directory.backups_path(), &project_name, &backup_id)?;
+    let project_directory = project_service::project_directory(&project.project_path);
+
+    let running_editor = state
+        .2
+        .lock()
+        .unwrap()
+        .running_editor(&project_name)
+        .map(|process| process.id)
+        .or_else(|| editor_lock::locked_editor_id(&project_directory));
+
+    if let Some(id) = running_editor {
+        return Err(editor_lock::already_open_message(&project_name, id));
+    }
+
+    project_backup::restore_backup(&backup, &project_directory)
+        .map_err(|error| format!("Could not restore backup {}: {}", backup_id, error))
+}
+
+#[tauri::command]
+async fn delete_project_backup(project_name: String, backup_id: String) -> Result<(), ()> {
+    let config_directory = ConfigDirectoryService::new();
+
+    let backup =
+        project_backup::find_backup(&config_directory.backups_path(), &project_name, &backup_id)
+            .map_err(|error| println!("{}", error))?;
+
+    project_backup::delete_backup(&backup)
+        .map_err(|error| println!("Could not delete backup {}: {}", backup_id, error))
+}
+
+#[tauri::command]
+async fn get_backup_settings() -> Result<BackupSettings, ()> {
+    let config_directory = ConfigDirectoryService::new();
+
+    Ok(config_directory_service::get_project_config(&config_directory).backup_settings)
+}
+
+#[tauri::command]
+async fn set_backup_settings(backup_settings: BackupSettings) -> Result<BackupSettings, ()> {
+    let config_directory = ConfigDirectoryService::new();
+
+    config_directory_service::save_backup_settings_to_config(&config_directory, &backup_settings);
+
+    Ok(backup_settings)
+}
+
+#[tauri::command]
+async fn get_project_cache_sizes(
+    state: tauri::State<'_, DataState>,
+) -> Result<Vec<ProjectCache>, ()> {
+    let projects = state.0.lock().await.projects.clone();
+
+    tokio::task::spawn_blocking(move || {
+        projects
+            .iter()
+            .map(|project| {
+                project_cache::project_cache(
+                    &project.project_name,
+                    &project_service::project_directory(&project.project_path),
+                )
+            })
+            .collect()
+    })
+    .await
+    .map_err(|error| println!("Could not measure project caches: {}", error))
+}
+
+/// Deletes the regenerable cache folders of the projects in `project_names`. Projects that have an editor open are skipped.
+#[tauri::command]
+async fn clean_project_caches(
+    state: tauri::State<'_, DataState>,
+    project_names: Vec<String>,
+    dry_run: bool,
+) -> Result<Vec<CacheCleanResult>, ()> {
+    let projects: Vec<ProjectData> = state
+        .0
+        .lock()
+        .await
+        .projects
+        .iter()
+        .filter(|project| project_names.contains(&project.project_name))
+        .cloned()
+        .collect();
+
+    let running_editors: HashMap<String, u32> = {
+        let registry = state.2.lock().unwrap();
+        projects
+            .iter()
+            .filter_map(|project| {
+                registry
+                    .running_editor(&project.project_name)
+                    .map(|process| (project.project_name.clone(), process.id))
+            })
+            .collect()
     };
 
-    #[tokio::test]
-    async fn test_reading_empty_config() {
-        fs::create_dir_all(".\\test-project\\projects\\project1".to_string()).unwrap();
-        fs::File::create(".\\test-project\\projects\\project1\\project.godot").unwrap();
+    tokio::task::spawn_blocking(move || {
+        project_cache::clean_project_caches(&projects, &running_editors, dry_run)
+    })
+    .await
+    .map_err(|error| println!("Could not clean project caches: {}", error))
+}
+
+#[tauri::command]
+async fn get_storage_report(state: tauri::State<'_, DataState>) -> Result<StorageReport, ()> {
+    let state_guard = state.0.lock().await;
+    let engines = state_guard.installed_godot_engine_versions.clone();
+    let projects = state_guard.projects.clone();
+
+    drop(state_guard);
 
-        let config = &ProjectConfig {
-            tracked_directories: vec![".\\test-project\\projects".to_string()],
-            tracked_godot_versions: vec![],
-            tracked_projects: vec![],
-        };
+    tokio::task::spawn_blocking(move || {
+        storage_report::storage_report(&ConfigDirectoryService::new(), &engines, &projects)
+    })
+    .await
+    .map_err(|error| println!("Could not measure storage: {}", error))
+}
+
+#[tauri::command]
+async fn get_dotnet_status(
+    state: tauri::State<'_, DataState>,
+    engine_name: String,
+) -> Result<DotnetStatus, ()> {
+    let state_guard = state.0.lock().await;
 
-        let config_directory = ConfigDirectoryService::new_test(
-            ".\\test-project-config".to_string(),
-            "config.json".to_string(),
-        );
+    let godot_engine = state_guard
+        .installed_godot_engine_versions
+        .iter()
+        .find(|engine| engine.version_name == engine_name)
+        .expect(format!("Did not find a godot engine with name {}", &engine_name).as_str())
+        .clone();
 
-        config_directory_service::save_project_config(&config_directory, config);
+    drop(state_guard);
 
-        let all_projects = get_all_projects_from_dirs(&config_directory);
+    Ok(dotnet_service::check_dotnet_for_engine(
+        &godot_engine,
+        dotnet_service::get_installed_dotnet_sdks(),
+    ))
+}
 
-        assert!(all_projects.len() == 1);
+/// Gets the cached news entries, refreshing them first if they are stale or `refresh` is set.
+/// The cached entries are returned if the news can't be fetched, e.g. while offline.
+#[tauri::command]
+async fn get_news_entries(
+    state: tauri::State<'_, DataState>,
+    refresh: Option<bool>,
+) -> Result<NewsResponse, ()> {
+    let config_directory = ConfigDirectoryService::new();
+    let settings = config_directory_service::get_project_config(&config_directory).news_settings;
+    let cache = news_cache::load_news_cache(&config_directory.news_path());
+
+    if !refresh.unwrap_or(false)
+        && !cache.is_stale(
+            settings.refresh_interval_minutes,
+            Local::now().timestamp_millis(),
+        )
+    {
+        return Ok(NewsResponse::from(&cache));
+    }
 
-        fs::remove_dir_all(".\\test-project").unwrap();
-        fs::remove_dir_all(".\\test-project-config").unwrap();
+    match news_cache::refresh_news(&config_directory, &state.3).await {
+        Ok(cache) => Ok(NewsResponse::from(&cache)),
+        Err(error) => {
+            println!("{}", error);
+            Ok(NewsResponse::from(&cache))
+        }
     }
 }
+
+/// Marks the news entries with the given `hrefs` as read, or as unread if `read` is false
+#[tauri::command]
+async fn mark_news_read(
+    state: tauri::State<'_, DataState>,
+    hrefs: Vec<String>,
+    read: bool,
+) -> Result<NewsResponse, ()> {
+    let config_directory = ConfigDirectoryService::new();
+
+    let cache = news_cache::update_news_cache(&config_directory.news_path(), &state.3, |cache| {
+        cache.set_read(&hrefs, read)
+    })
+    .await
+    .map_err(|error| println!("Could not save the news cache: {}", error))?;
+
+    Ok(NewsResponse::from(&cache))
+}
+
+#[tauri::command]
+async fn get_news_settings() -> Result<NewsSettings, ()> {
+    let config_directory = ConfigDirectoryService::new();
+
+    Ok(config_directory_service::get_project_config(&config_directory).news_settings)
+}
+
+#[tauri::command]
+async fn set_news_settings(news_settings: NewsSettings) -> Result<NewsSettings, ()> {
+    let config_directory = ConfigDirectoryService::new();
+
+    config_directory_service::save_news_settings_to_config(&config_directory, &news_settings);
+
+    Ok(news_settings)
+}
+
+/// Refreshes the news whenever the cache goes stale and sends the result to the frontend as a `news-updated` event.
+/// The settings are read every minute so a changed interval applies without a restart.
+async fn refresh_news_periodically(app: AppHandle, news_lock: Arc<Mutex<()>>) {
+    let config_directory = ConfigDirectoryService::new();
+
+    loop {
+        let settings =
+            config_directory_service::get_project_config(&config_directory).news_settings;
+        let cache = news_cache::load_news_cache(&config_directory.news_path());
+
+        if settings.refresh_interval_minutes > 0
+            && cache.is_stale(
+                settings.refresh_interval_minutes,
+                Local::now().timestamp_millis(),
+            )
+        {
+            match news_cache::refresh_news(&config_directory, &news_lock).await {
+                Ok(cache) => {
+                    app.emit("news-updated", NewsResponse::from(&cache)).ok();
+                }
+                Err(error) => println!("{}", error),
+            }
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if cli::cli::is_cli_invocation(&args) {
+        cli::cli::attach_console();
+        let exit_code = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(cli::cli::run(args));
+        std::process::exit(exit_code);
+    }
+
+    let state = DataState(
+        Mutex::new(Data {
+            all_godot_engine_versions: vec![],
+            installed_godot_engine_versions: vec![],
+            projects: vec![],
+        }),
+        std::sync::Mutex::new(HashMap::new()),
+        std::sync::Mutex::new(ProcessRegistry::new()),
+        Arc::new(Mutex::new(())),
+    );
+    let news_lock = state.3.clone();
+
+    tauri::Builder::default()
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_shell::init())
+        .manage(state)
+        .setup(|app| {
+            tauri::async_runtime::spawn(refresh_news_periodically(app.handle().clone(), news_lock));
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            get_engine_versions,
+            get_engine_updates,
+            get_release_filter,
+            set_release_filter,
+            get_latest_stable,
+            get_release_notes,
+            upgrade_engine,
+            download_engine_version,
+            get_installed_versions,
+            remove_installed_version,
+            set_engine_self_contained,
+            install_export_templates,
+            get_all_projects,
+            query_projects,
+            get_project_tags,
+            set_project_metadata,
+            get_project_thumbnails,
+            save_project_path,
+            get_project_paths,
+            remove_project_path,
+            set_engine_version_for_project,
+            poll_download_status_list,
+            open_project,
+            get_project_scenes,
+            run_project,
+            get_export_presets,
+            run_project_task,
+            get_processes,
+            get_process_log,
+            clear_exited_processes,
+            stop_process,
+            get_project_stats,
+            get_launch_profiles,
+            save_launch_profile,
+            remove_launch_profile,
+            set_default_launch_profile,
+            open_engine,
+            check_project_compatibility,
+            diagnose_project,
+            upgrade_project,
+            create_project_backup,
+            get_project_backups,
+            restore_project_backup,
+            delete_project_backup,
+            get_backup_settings,
+            set_backup_settings,
+            get_project_cache_sizes,
+            clean_project_caches,
+            get_storage_report,
+            get_dotnet_status,
+            get_news_entries,
+            mark_news_read,
+            get_news_settings,
+            set_news_settings,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
@@ -1,2 +0,0 @@
-pub mod project_data;
-pub mod project_service;
@@ -5,11 +5,15 @@ import { PageEnum } from "./data/PageEnum";
 import { GodotEngineVersion } from "./data/GodotEngineVersion";
 import { invoke } from "@tauri-apps/api/core"
 import { GodotEngineResponse } from "./data/GodotEngineResponse";
+import { EngineRemovalResponse } from "./data/EngineRemovalResponse";
+import { EngineUpdate } from "./data/EngineUpdate";
+import { EngineUpgradeResponse } from "./data/EngineUpgradeResponse";
 import { ProjectData } from "./data/ProjectData";
 import ProjectPage from "./components/ProjectPage";
 import EnginePage from "./components/EnginePage";
 import SettingsPage from "./components/SettingsPage";
 import { NewsEntry } from "./data/NewsEntry";
+import { NewsResponse } from "./data/NewsResponse";
 import NewsPage from "./components/NewsPage";
 import { OrbitProgress } from "react-loading-indicators";
 
@@ -25,6 +29,7 @@ function App() {
   const [loading, setLoading] = useState(true);
   const [allEngines, setAllEngines] = useState<GodotEngineVersion[]>([]);
   const [installedEngines, setInstalledEngines] = useState<GodotEngineVersion[]>([]);
+  const [engineUpdates, setEngineUpdates] = useState<EngineUpdate[]>([]);
   const [projects, setProjects] = useState<ProjectData[]>([]);
   const [projectPaths, setProjectPaths] = useState<string[]>([]);
   const [newsEntries, setNewsEntries] = useState<NewsEntry[]>([]);
@@ -44,6 +49,7 @@ function App() {
 
     let allEngines = await invoke<GodotEngineResponse>("get_engine_versions");
     setAllEngines(allEngines.allVersions);
+    setEngineUpdates(allEngines.updates);
 
 
     let installedVersions = await invoke<GodotEngineVersion[]>("get_installed_versions");
@@ -56,8 +62,8 @@ function App() {
       setProjectPaths(response);
     })
 
-    invoke<NewsEntry[]>("get_news_entries").then(response => {
-      setNewsEntries(response);
+    invoke<NewsResponse>("get_news_entries").then(response => {
+      setNewsEntries(response.entries);
     })
 
     setLoading(false);
@@ -76,12 +82,28 @@ function App() {
     })
   }
 
-  function deleteVersion(engineName: string) {
-    invoke<GodotEngineVersion[]>("remove_installed_version", { engineVersionName: engineName }).then(response => {
-      setInstalledEngines(response);
+  function deleteVersion(engineName: string, force: boolean = false) {
+    invoke<EngineRemovalResponse>("remove_installed_version", { engineVersionName: engineName, force: force }).then(response => {
+      if (!response.removed) {
+        if (window.confirm(`${engineName} is used by ${response.dependents.join(", ")}.\n\nRemove it anyway?`)) {
+          deleteVersion(engineName, true);
+        }
+        return;
+      }
+      setInstalledEngines(response.installedVersions);
+      setProjects(response.projects);
     })
   }
 
+  function upgradeEngine(update: EngineUpdate) {
+    let reassignProjects = window.confirm(`Install ${update.latestName}.\n\nMove the projects using ${update.engineName} to it?`);
+    invoke<EngineUpgradeResponse>("upgrade_engine", { engineName: update.engineName, reassignProjects: reassignProjects }).then(response => {
+      setInstalledEngines(response.installedVersions);
+      setProjects(response.projects);
+      invoke<EngineUpdate[]>("get_engine_updates").then(updates => setEngineUpdates(updates));
+    }).catch(error => window.alert(error))
+  }
+
   function downloadEngine(engineName: string) {
     invoke("download_engine_version", { engineName: engineName }).then(_ => {
       invoke<GodotEngineVersion[]>("get_installed_versions").then(response => {
@@ -121,7 +143,7 @@ function App() {
             : page == PageEnum.Projects ? (
               <ProjectPage installedGodotEngines={installedEngines} allProjects={projects} setAllProjects={setProjects} setProjectEngineVersion={setProjectEngineVersion} />
             ) : page == PageEnum.Engines ? (
-              <EnginePage allGodotEngines={allEngines} installedGodotEngines={installedEngines} downloadEngineFunc={downloadEngine} deleteVersion={deleteVersion} />
+              <EnginePage allGodotEngines={allEngines} installedGodotEngines={installedEngines} downloadEngineFunc={downloadEngine} deleteVersion={deleteVersion} engineUpdates={engineUpdates} upgradeEngine={upgradeEngine} />
             ) : page == PageEnum.Settings ? (
               <SettingsPage initialProjectPaths={projectPaths} refreshProjects={getAllProjects} />
             ) : page == PageEnum.News ? (
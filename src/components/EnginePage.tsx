@@ -1,9 +1,11 @@
 import { ReactNode, useEffect, useState } from "react";
 import { GodotEngineVersion } from "../data/GodotEngineVersion";
+import { EngineUpdate } from "../data/EngineUpdate";
 import styles from "../css-modules/EnginePage.module.css";
 import DownloadIcon from '@mui/icons-material/Download';
 import DeleteForeverIcon from '@mui/icons-material/DeleteForever';
 import PlayArrowIcon from '@mui/icons-material/PlayArrow';
+import UpgradeIcon from '@mui/icons-material/Upgrade';
 import { IconButton } from "@mui/material";
 import { invoke } from "@tauri-apps/api/core"
 
@@ -12,6 +14,8 @@ interface EnginePageProps {
     installedGodotEngines: GodotEngineVersion[];
     downloadEngineFunc: (engineName: string) => void;
     deleteVersion: (engineName: string) => void
+    engineUpdates: EngineUpdate[];
+    upgradeEngine: (update: EngineUpdate) => void;
 }
 
 function EnginePage(props: EnginePageProps) {
@@ -60,8 +64,14 @@ function EnginePage(props: EnginePageProps) {
     function table(engines: GodotEngineVersion[], buttonType: "install" | "delete"): ReactNode {
 
         function getButton(engine: GodotEngineVersion) {
+            let update = props.engineUpdates.find(update => update.engineName === engine.engineName);
             return buttonType === "install" ? (
                 <div>
+                    {update ?
+                        <IconButton title={`Upgrade to ${update.latestVersion}`} onClick={() => props.upgradeEngine(update)}>
+                            <UpgradeIcon color="primary" />
+                        </IconButton>
+                        : null}
                     <IconButton onClick={() => props.deleteVersion(engine.engineName)}>
                         <DeleteForeverIcon color={"error"} />
                     </IconButton>
@@ -87,7 +97,7 @@ function EnginePage(props: EnginePageProps) {
                 {engines.map((engine, idx) => (
                     <tr key={idx}>
                         <td>{engine.engineName}</td>
-                        <td>{engine.engineVersion}</td>
+                        <td>{engine.engineVersion}{engine.isMono ? " .NET" : ""}</td>
                         <td>{new Date(engine.updatedAt).toDateString()}</td>
                         <td>
                             {getButton(engine)}
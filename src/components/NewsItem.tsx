@@ -1,6 +1,7 @@
 import React from 'react';
 import styles from '../css-modules/NewsItem.module.css';
 import { NewsEntry } from '../data/NewsEntry';
+import { convertFileSrc, invoke } from '@tauri-apps/api/core';
 
 interface NewsItemProps {
     entry: NewsEntry;
@@ -8,9 +9,9 @@ interface NewsItemProps {
 
 const NewsItem: React.FC<NewsItemProps> = ({ entry }) => {
     return (
-        <a href={`https://godotengine.org/${entry.href}`} target="_blank" rel="noopener noreferrer">
+        <a href={entry.href} target="_blank" rel="noopener noreferrer" onClick={() => invoke("mark_news_read", { hrefs: [entry.href], read: true })}>
             <div className={styles.newsItem}>
-                <img src={entry.image_url} alt={entry.title} className={styles.image} />
+                <img src={entry.image_path ? convertFileSrc(entry.image_path) : entry.image_url} alt={entry.title} className={styles.image} />
                 <div className={styles.content}>
                     <h2 className={styles.title}>{entry.title}</h2>
                     <p className={styles.info}>{entry.info}</p>
@@ -5,6 +5,8 @@ import PlayArrowIcon from '@mui/icons-material/PlayArrow';
 import { IconButton } from "@mui/material";
 import { convertFileSrc, invoke } from "@tauri-apps/api/core"
 import ArrowDropDownIcon from '@mui/icons-material/ArrowDropDown';
+import { useEffect, useState } from "react";
+import { ProjectThumbnail } from "../data/ProjectThumbnail";
 
 interface ProjectPageProps {
     installedGodotEngines: GodotEngineVersion[];
@@ -15,7 +17,11 @@ interface ProjectPageProps {
 
 function ProjectPage(props: ProjectPageProps) {
     // const [projects, setProjects] = useState(props.allProjects);
+    const [thumbnails, setThumbnails] = useState<Record<string, ProjectThumbnail>>({});
 
+    useEffect(() => {
+        invoke<Record<string, ProjectThumbnail>>("get_project_thumbnails").then(setThumbnails);
+    }, [props.allProjects]);
 
     function selectVersion(projectData: ProjectData, event: React.FormEvent<HTMLSelectElement>): void {
         props.setProjectEngineVersion(projectData.projectName, event.currentTarget.value)
@@ -52,8 +58,17 @@ function ProjectPage(props: ProjectPageProps) {
         return props.installedGodotEngines.find(engine => engine.engineName == engineName) ?? null;
     }
 
-    async function launch(project: ProjectData) {
-        const [name, time] = await invoke<[string, number]>("open_project", { projectName: project.projectName });
+    async function launch(project: ProjectData, force: boolean = false) {
+        let name: string;
+        let time: number;
+        try {
+            [name, time] = await invoke<[string, number]>("open_project", { projectName: project.projectName, force: force });
+        } catch (error) {
+            if (!force && window.confirm(`${error}\n\nOpen it anyway?`)) {
+                await launch(project, true);
+            }
+            return;
+        }
         let p = props.allProjects.find(p => p.projectName === name);
         if (p) {
             p.lastDateOpened = time;
@@ -62,6 +77,11 @@ function ProjectPage(props: ProjectPageProps) {
     }
 
     function getImagePath(project: ProjectData): string {
+        const iconPath = thumbnails[project.projectName]?.iconPath;
+        if (iconPath) {
+            return convertFileSrc(iconPath);
+        }
+
         const indexLastSlah = project.projectPath.lastIndexOf("\\");
         const path = project.projectPath.substring(0, indexLastSlah);
         return convertFileSrc(path + "\\icon.png");
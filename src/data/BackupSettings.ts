@@ -0,0 +1,11 @@
+export class BackupSettings {
+    excludeGlobs: string[];
+    keepLast: number | null;
+    keepDays: number | null;
+
+    constructor(excludeGlobs: string[], keepLast: number | null, keepDays: number | null) {
+        this.excludeGlobs = excludeGlobs;
+        this.keepLast = keepLast;
+        this.keepDays = keepDays;
+    }
+}
@@ -0,0 +1,13 @@
+export type Severity = "Info" | "Warning" | "Error";
+
+export class Diagnostic {
+    severity: Severity;
+    message: string;
+    suggestedFix: string;
+
+    constructor(severity: Severity, message: string, suggestedFix: string) {
+        this.severity = severity;
+        this.message = message;
+        this.suggestedFix = suggestedFix;
+    }
+}
@@ -0,0 +1,16 @@
+import { GodotEngineVersion } from "./GodotEngineVersion";
+import { ProjectData } from "./ProjectData";
+
+export class EngineRemovalResponse {
+    removed: boolean;
+    dependents: string[];
+    installedVersions: GodotEngineVersion[];
+    projects: ProjectData[];
+
+    constructor(removed: boolean, dependents: string[], installedVersions: GodotEngineVersion[], projects: ProjectData[]) {
+        this.removed = removed;
+        this.dependents = dependents;
+        this.installedVersions = installedVersions;
+        this.projects = projects;
+    }
+}
@@ -0,0 +1,11 @@
+export class EngineUpdate {
+    engineName: string;
+    latestName: string;
+    latestVersion: string;
+
+    constructor(engineName: string, latestName: string, latestVersion: string) {
+        this.engineName = engineName;
+        this.latestName = latestName;
+        this.latestVersion = latestVersion;
+    }
+}
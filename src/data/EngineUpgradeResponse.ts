@@ -0,0 +1,16 @@
+import { GodotEngineVersion } from "./GodotEngineVersion";
+import { ProjectData } from "./ProjectData";
+
+export class EngineUpgradeResponse {
+    engine: GodotEngineVersion;
+    reassigned: string[];
+    installedVersions: GodotEngineVersion[];
+    projects: ProjectData[];
+
+    constructor(engine: GodotEngineVersion, reassigned: string[], installedVersions: GodotEngineVersion[], projects: ProjectData[]) {
+        this.engine = engine;
+        this.reassigned = reassigned;
+        this.installedVersions = installedVersions;
+        this.projects = projects;
+    }
+}
@@ -0,0 +1,20 @@
+export class ExportPreset {
+    index: number;
+    name: string;
+    platform: string;
+    exportPath: string;
+    runnable: boolean;
+
+    constructor(index: number, name: string, platform: string, exportPath: string, runnable: boolean) {
+        this.index = index;
+        this.name = name;
+        this.platform = platform;
+        this.exportPath = exportPath;
+        this.runnable = runnable;
+    }
+}
+
+export type ProjectTask =
+    | { type: "Export"; preset: string; outputPath?: string; debug: boolean }
+    | { type: "Import" }
+    | { type: "Script"; path: string };
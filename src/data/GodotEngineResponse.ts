@@ -1,8 +1,12 @@
+import { EngineUpdate } from "./EngineUpdate";
 import { GodotEngineVersion } from "./GodotEngineVersion";
+import { ReleaseGroup } from "./ReleaseGroup";
 
 export class GodotEngineResponse {
     allVersions: GodotEngineVersion[];
     installedVersions: GodotEngineVersion[];
+    groups: ReleaseGroup[] = [];
+    updates: EngineUpdate[] = [];
 
     constructor(allVersions: GodotEngineVersion[], installedVersions: GodotEngineVersion[]) {
         this.allVersions = allVersions;
@@ -4,11 +4,17 @@ export class GodotEngineVersion {
     engineVersion: string;
     installationPath: String;
     updatedAt: number;
+    isMono: boolean;
+    exportTemplatesInstalled: boolean;
+    selfContained: boolean;
 
-    constructor(engineName: string, engineVersion: string, installationPath: String, updatedAt: string) {
+    constructor(engineName: string, engineVersion: string, installationPath: String, updatedAt: string, isMono: boolean, exportTemplatesInstalled: boolean, selfContained: boolean) {
         this.engineName = engineName;
         this.engineVersion = engineVersion;
         this.installationPath = installationPath;
         this.updatedAt = Date.parse(updatedAt);
+        this.isMono = isMono;
+        this.exportTemplatesInstalled = exportTemplatesInstalled;
+        this.selfContained = selfContained;
     }
 }
\ No newline at end of file
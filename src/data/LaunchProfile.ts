@@ -0,0 +1,17 @@
+export type LaunchMode = "Editor" | "Game";
+
+export class LaunchProfile {
+    name: string;
+    mode: LaunchMode;
+    arguments: string[];
+    environmentVariables: Record<string, string>;
+    workingDirectory: string | null;
+
+    constructor(name: string, mode: LaunchMode, args: string[], environmentVariables: Record<string, string>, workingDirectory: string | null) {
+        this.name = name;
+        this.mode = mode;
+        this.arguments = args;
+        this.environmentVariables = environmentVariables;
+        this.workingDirectory = workingDirectory;
+    }
+}
@@ -4,6 +4,9 @@ export class NewsEntry {
     body: string;
     image_url: string;
     href: string;
+    fetched_at: number = 0;
+    image_path: string | null = null;
+    read: boolean = false;
 
     constructor(title: string, info: string, body: string, image_url: string, href: string) {
         this.title = title;
@@ -0,0 +1,13 @@
+import { NewsEntry } from "./NewsEntry";
+
+export class NewsResponse {
+    entries: NewsEntry[];
+    unreadCount: number;
+    fetchedAt: number | null;
+
+    constructor(entries: NewsEntry[], unreadCount: number, fetchedAt: number | null) {
+        this.entries = entries;
+        this.unreadCount = unreadCount;
+        this.fetchedAt = fetchedAt;
+    }
+}
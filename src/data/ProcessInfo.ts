@@ -0,0 +1,31 @@
+export type ProcessKind = "Editor" | "Game" | "Task";
+
+export class ProcessInfo {
+    id: number;
+    projectName: string | null;
+    engineName: string;
+    kind: ProcessKind;
+    startedAt: number;
+    logPath: string;
+    exitCode: number | null;
+    exitedAt: number | null;
+    crashed: boolean;
+
+    constructor(id: number, projectName: string | null, engineName: string, kind: ProcessKind, startedAt: number, logPath: string) {
+        this.id = id;
+        this.projectName = projectName;
+        this.engineName = engineName;
+        this.kind = kind;
+        this.startedAt = startedAt;
+        this.logPath = logPath;
+        this.exitCode = null;
+        this.exitedAt = null;
+        this.crashed = false;
+    }
+}
+
+export type ProcessOutput = {
+    id: number;
+    stream: "Stdout" | "Stderr";
+    line: string;
+};
@@ -0,0 +1,21 @@
+export class ProjectBackup {
+    id: string;
+    projectName: string;
+    projectPath: string;
+    engineVersion: string;
+    createdAt: number;
+    archivePath: string;
+    size: number;
+    excludeGlobs: string[];
+
+    constructor(id: string, projectName: string, projectPath: string, engineVersion: string, createdAt: number, archivePath: string, size: number, excludeGlobs: string[]) {
+        this.id = id;
+        this.projectName = projectName;
+        this.projectPath = projectPath;
+        this.engineVersion = engineVersion;
+        this.createdAt = createdAt;
+        this.archivePath = archivePath;
+        this.size = size;
+        this.excludeGlobs = excludeGlobs;
+    }
+}
@@ -0,0 +1,35 @@
+export class CacheFolder {
+    path: string;
+    size: number;
+
+    constructor(path: string, size: number) {
+        this.path = path;
+        this.size = size;
+    }
+}
+
+export class ProjectCache {
+    projectName: string;
+    folders: CacheFolder[];
+    totalSize: number;
+
+    constructor(projectName: string, folders: CacheFolder[], totalSize: number) {
+        this.projectName = projectName;
+        this.folders = folders;
+        this.totalSize = totalSize;
+    }
+}
+
+export class CacheCleanResult {
+    projectName: string;
+    dryRun: boolean;
+    freedBytes: number;
+    error: string | null;
+
+    constructor(projectName: string, dryRun: boolean, freedBytes: number, error: string | null) {
+        this.projectName = projectName;
+        this.dryRun = dryRun;
+        this.freedBytes = freedBytes;
+        this.error = error;
+    }
+}
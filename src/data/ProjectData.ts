@@ -1,3 +1,5 @@
+import { LaunchProfile } from "./LaunchProfile";
+
 
 export class ProjectData {
     projectName: string;
@@ -6,13 +8,21 @@ export class ProjectData {
     engineVersion: string;
     favorite: boolean;
     engineValid: boolean;
+    isCSharp: boolean;
+    launchProfiles: LaunchProfile[] = [];
+    defaultLaunchProfile: string | null = null;
+    tags: string[] = [];
+    note: string = "";
+    colorLabel: string | null = null;
+    pinEngine: boolean = false;
 
-    constructor(projectName: string, path: string, lastOpened: string, engineVersion: string, favorite: boolean, engineValid: boolean) {
+    constructor(projectName: string, path: string, lastOpened: string, engineVersion: string, favorite: boolean, engineValid: boolean, isCSharp: boolean) {
         this.projectName = projectName;
         this.projectPath = path;
         this.lastDateOpened = Date.parse(lastOpened);
         this.engineVersion = engineVersion;
         this.favorite = favorite;
         this.engineValid = engineValid;
+        this.isCSharp = isCSharp;
     }
 }
\ No newline at end of file
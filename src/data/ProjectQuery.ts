@@ -0,0 +1,10 @@
+export type ProjectSortKey = "Name" | "LastOpened" | "EngineVersion" | "Tag" | "Favorite";
+
+export class ProjectQuery {
+    search: string | null = null;
+    engineVersion: string | null = null;
+    tag: string | null = null;
+    favoritesOnly: boolean = false;
+    sortBy: ProjectSortKey = "LastOpened";
+    descending: boolean = false;
+}
@@ -0,0 +1,35 @@
+export class UsageSession {
+    openedAt: number;
+    closedAt: number | null;
+    engineName: string;
+    exitCode: number | null;
+    crashed: boolean;
+
+    constructor(openedAt: number, closedAt: number | null, engineName: string, exitCode: number | null, crashed: boolean) {
+        this.openedAt = openedAt;
+        this.closedAt = closedAt;
+        this.engineName = engineName;
+        this.exitCode = exitCode;
+        this.crashed = crashed;
+    }
+}
+
+export class ProjectStats {
+    timesOpened: number;
+    totalEditorHours: number;
+    timesOpenedLast30Days: number;
+    editorHoursLast30Days: number;
+    crashes: number;
+    lastOpened: number | null;
+    sessions: UsageSession[];
+
+    constructor(timesOpened: number, totalEditorHours: number, timesOpenedLast30Days: number, editorHoursLast30Days: number, crashes: number, lastOpened: number | null, sessions: UsageSession[]) {
+        this.timesOpened = timesOpened;
+        this.totalEditorHours = totalEditorHours;
+        this.timesOpenedLast30Days = timesOpenedLast30Days;
+        this.editorHoursLast30Days = editorHoursLast30Days;
+        this.crashes = crashes;
+        this.lastOpened = lastOpened;
+        this.sessions = sessions;
+    }
+}
@@ -0,0 +1,9 @@
+export class ProjectThumbnail {
+    iconPath: string | null;
+    screenshotPath: string | null;
+
+    constructor(iconPath: string | null, screenshotPath: string | null) {
+        this.iconPath = iconPath;
+        this.screenshotPath = screenshotPath;
+    }
+}
@@ -0,0 +1,12 @@
+export type ReleaseChannel = "Stable" | "Rc" | "Beta" | "Alpha" | "Dev";
+export type ReleaseFlavor = "Standard" | "Mono";
+
+export class ReleaseFilter {
+    channels: ReleaseChannel[];
+    flavors: ReleaseFlavor[];
+
+    constructor(channels: ReleaseChannel[], flavors: ReleaseFlavor[]) {
+        this.channels = channels;
+        this.flavors = flavors;
+    }
+}
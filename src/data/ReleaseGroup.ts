@@ -0,0 +1,14 @@
+import { GodotEngineVersion } from "./GodotEngineVersion";
+import { ReleaseChannel, ReleaseFlavor } from "./ReleaseFilter";
+
+export class ReleaseGroup {
+    channel: ReleaseChannel;
+    flavor: ReleaseFlavor;
+    engines: GodotEngineVersion[];
+
+    constructor(channel: ReleaseChannel, flavor: ReleaseFlavor, engines: GodotEngineVersion[]) {
+        this.channel = channel;
+        this.flavor = flavor;
+        this.engines = engines;
+    }
+}
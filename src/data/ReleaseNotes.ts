@@ -0,0 +1,13 @@
+export class ReleaseNotes {
+    tagName: string;
+    body: string;
+    htmlUrl: string;
+    publishedAt: string | null;
+
+    constructor(tagName: string, body: string, htmlUrl: string, publishedAt: string | null) {
+        this.tagName = tagName;
+        this.body = body;
+        this.htmlUrl = htmlUrl;
+        this.publishedAt = publishedAt;
+    }
+}
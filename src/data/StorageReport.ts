@@ -0,0 +1,53 @@
+import { ProjectCache } from "./ProjectCache";
+
+export class EngineUsage {
+    engineName: string;
+    installSize: number;
+    archivePaths: string[];
+    archiveSize: number;
+    exportTemplatesSize: number;
+    usedBy: string[];
+
+    constructor(engineName: string, installSize: number, archivePaths: string[], archiveSize: number, exportTemplatesSize: number, usedBy: string[]) {
+        this.engineName = engineName;
+        this.installSize = installSize;
+        this.archivePaths = archivePaths;
+        this.archiveSize = archiveSize;
+        this.exportTemplatesSize = exportTemplatesSize;
+        this.usedBy = usedBy;
+    }
+}
+
+export class UnregisteredEngineFolder {
+    path: string;
+    size: number;
+
+    constructor(path: string, size: number) {
+        this.path = path;
+        this.size = size;
+    }
+}
+
+export class StorageReport {
+    engines: EngineUsage[];
+    unregisteredEngines: UnregisteredEngineFolder[];
+    trashSize: number;
+    downloadCacheSize: number;
+    backupsSize: number;
+    logsSize: number;
+    projectCaches: ProjectCache[];
+    totalSize: number;
+    suggestions: string[];
+
+    constructor(engines: EngineUsage[], unregisteredEngines: UnregisteredEngineFolder[], trashSize: number, downloadCacheSize: number, backupsSize: number, logsSize: number, projectCaches: ProjectCache[], totalSize: number, suggestions: string[]) {
+        this.engines = engines;
+        this.unregisteredEngines = unregisteredEngines;
+        this.trashSize = trashSize;
+        this.downloadCacheSize = downloadCacheSize;
+        this.backupsSize = backupsSize;
+        this.logsSize = logsSize;
+        this.projectCaches = projectCaches;
+        this.totalSize = totalSize;
+        this.suggestions = suggestions;
+    }
+}
@@ -0,0 +1,13 @@
+export class UpgradeReport {
+    dryRun: boolean;
+    success: boolean;
+    output: string[];
+    backupPath: string | null;
+
+    constructor(dryRun: boolean, success: boolean, output: string[], backupPath: string | null) {
+        this.dryRun = dryRun;
+        this.success = success;
+        this.output = output;
+        this.backupPath = backupPath;
+    }
+}